@@ -0,0 +1,19 @@
+-- =========================
+-- refresh_tokens (rotating, grouped by family)
+-- =========================
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,     -- sha256 hex, raw token is never stored
+    expires_at TIMESTAMP NOT NULL,
+    rotated_at TIMESTAMP,                       -- set once exchanged for a new token
+    revoked_at TIMESTAMP,                       -- set when the family is revoked
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family
+    ON refresh_tokens (family_id);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_user
+    ON refresh_tokens (user_id);
@@ -0,0 +1,51 @@
+-- =========================
+-- roles
+-- =========================
+CREATE TABLE IF NOT EXISTS roles (
+    name VARCHAR(32) PRIMARY KEY,          -- 'admin', 'staff', 'member'
+    description VARCHAR(255) NOT NULL
+);
+
+-- =========================
+-- role_permissions
+-- =========================
+CREATE TABLE IF NOT EXISTS role_permissions (
+    role VARCHAR(32) NOT NULL REFERENCES roles(name) ON DELETE CASCADE,
+    permission VARCHAR(64) NOT NULL,       -- 'users:read', 'users:delete', ...
+    PRIMARY KEY (role, permission)
+);
+
+-- =========================
+-- user_roles
+-- =========================
+CREATE TABLE IF NOT EXISTS user_roles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(32) NOT NULL REFERENCES roles(name) ON DELETE CASCADE,
+    assigned_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, role)
+);
+
+INSERT INTO roles (name, description) VALUES
+    ('admin', 'Full access to every resource'),
+    ('staff', 'Read users and manage schools'),
+    ('member', 'Regular account')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'users:read'),
+    ('admin', 'users:write'),
+    ('admin', 'users:delete'),
+    ('admin', 'roles:manage'),
+    ('admin', 'schools:read'),
+    ('admin', 'schools:write'),
+    ('admin', 'schools:delete'),
+    ('staff', 'users:read'),
+    ('staff', 'schools:read'),
+    ('staff', 'schools:write'),
+    ('member', 'schools:read')
+ON CONFLICT (role, permission) DO NOTHING;
+
+-- Every existing account starts as a member
+INSERT INTO user_roles (user_id, role)
+SELECT id, 'member' FROM users
+ON CONFLICT (user_id, role) DO NOTHING;
@@ -0,0 +1,13 @@
+-- =========================
+-- schools (localized name / acronym stored as JSONB, e.g. {"th": "...", "en": "..."})
+-- =========================
+CREATE TABLE IF NOT EXISTS schools (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name JSONB NOT NULL,
+    acronym JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_schools_created_at
+    ON schools (created_at);
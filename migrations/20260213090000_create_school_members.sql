@@ -0,0 +1,17 @@
+-- =========================
+-- school_members (users ↔ schools with a per-school role)
+-- =========================
+CREATE TABLE IF NOT EXISTS school_members (
+    school_id UUID NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(16) NOT NULL CHECK (role IN ('student', 'teacher', 'admin')),
+    enrolled_at DATE NOT NULL DEFAULT CURRENT_DATE,
+    left_at DATE,                                -- NULL while the membership is active
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (school_id, user_id),
+    CHECK (left_at IS NULL OR left_at >= enrolled_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_school_members_user
+    ON school_members (user_id);
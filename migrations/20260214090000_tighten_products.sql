@@ -0,0 +1,20 @@
+-- products was created with nullable timestamps and no price guard
+UPDATE products SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+UPDATE products SET updated_at = created_at WHERE updated_at IS NULL;
+
+ALTER TABLE products
+    ALTER COLUMN created_at SET NOT NULL,
+    ALTER COLUMN updated_at SET NOT NULL,
+    ADD CONSTRAINT products_price_non_negative CHECK (price >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_products_price
+    ON products (price);
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'products:read'),
+    ('admin', 'products:write'),
+    ('admin', 'products:delete'),
+    ('staff', 'products:read'),
+    ('staff', 'products:write'),
+    ('member', 'products:read')
+ON CONFLICT (role, permission) DO NOTHING;
@@ -0,0 +1,14 @@
+-- =========================
+-- password_reset_tokens (single use, short lived)
+-- =========================
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,     -- sha256 hex, raw token is only ever emailed
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,                          -- set once redeemed or superseded
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user
+    ON password_reset_tokens (user_id);
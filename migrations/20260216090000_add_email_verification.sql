@@ -0,0 +1,23 @@
+-- =========================
+-- users.verified_at
+-- =========================
+ALTER TABLE users ADD COLUMN IF NOT EXISTS verified_at TIMESTAMP;
+
+-- accounts created before verification existed are grandfathered in
+UPDATE users SET verified_at = created_at WHERE verified_at IS NULL;
+
+-- =========================
+-- email_verification_tokens (single use, bound to one address)
+-- =========================
+CREATE TABLE IF NOT EXISTS email_verification_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,                -- address the token proves ownership of
+    token_hash VARCHAR(64) NOT NULL UNIQUE,     -- sha256 hex, raw token is only ever emailed
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,                          -- set once redeemed or superseded
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_verification_tokens_user
+    ON email_verification_tokens (user_id);
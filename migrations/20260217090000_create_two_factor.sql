@@ -0,0 +1,44 @@
+-- =========================
+-- user_totp (one authenticator per user)
+-- =========================
+CREATE TABLE IF NOT EXISTS user_totp (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,                -- base32, needed in clear to compute codes
+    confirmed_at TIMESTAMP,                     -- NULL while enrollment is pending
+    last_used_step BIGINT,                      -- time step of the last accepted code, blocks replays
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- =========================
+-- user_recovery_codes (single use)
+-- =========================
+CREATE TABLE IF NOT EXISTS user_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,             -- sha256 hex, raw code is only shown once
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_recovery_codes_user
+    ON user_recovery_codes (user_id);
+
+-- =========================
+-- two_factor_challenges (partial login awaiting a second factor)
+-- =========================
+CREATE TABLE IF NOT EXISTS two_factor_challenges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    consumed_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_two_factor_challenges_user
+    ON two_factor_challenges (user_id);
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'users:2fa:reset')
+ON CONFLICT (role, permission) DO NOTHING;
@@ -0,0 +1,19 @@
+-- =========================
+-- login_throttles (failed logins per account and per client IP)
+-- =========================
+CREATE TABLE IF NOT EXISTS login_throttles (
+    scope VARCHAR(16) NOT NULL,                 -- 'account' (normalized email) or 'ip'
+    key VARCHAR(255) NOT NULL,
+    failed_count INT NOT NULL DEFAULT 0,        -- failures since the last lockout
+    lockout_count INT NOT NULL DEFAULT 0,       -- drives the exponential backoff
+    locked_until TIMESTAMP,
+    last_failed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (scope, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_throttles_last_failed
+    ON login_throttles (last_failed_at);
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'users:unlock')
+ON CONFLICT (role, permission) DO NOTHING;
@@ -0,0 +1,18 @@
+-- =========================
+-- api_keys (long-lived credentials for machine clients)
+-- =========================
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    prefix VARCHAR(16) NOT NULL,                -- leading characters of the key, safe to display
+    key_hash VARCHAR(64) NOT NULL UNIQUE,       -- sha256 hex, raw key is only shown once
+    scopes TEXT[] NOT NULL,                     -- permissions the key may use, e.g. 'users:read'
+    expires_at TIMESTAMP,                       -- NULL never expires
+    last_used_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user
+    ON api_keys (user_id);
@@ -0,0 +1,18 @@
+-- =========================
+-- sessions (cookie authentication)
+-- =========================
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,     -- sha256 hex of the cookie value
+    csrf_hash VARCHAR(64) NOT NULL,             -- sha256 hex of the CSRF token
+    user_agent VARCHAR(255),
+    ip VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, -- drives the idle timeout
+    expires_at TIMESTAMP NOT NULL,              -- absolute timeout
+    revoked_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_user
+    ON sessions (user_id);
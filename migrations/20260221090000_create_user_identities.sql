@@ -0,0 +1,30 @@
+-- =========================
+-- user_identities (accounts at external OIDC providers)
+-- =========================
+CREATE TABLE IF NOT EXISTS user_identities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(64) NOT NULL,              -- configured provider name
+    subject VARCHAR(255) NOT NULL,              -- `sub` claim, stable per provider
+    email VARCHAR(255) NOT NULL,                -- verified email it was linked by
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_login_at TIMESTAMP,
+    UNIQUE (provider, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_identities_user
+    ON user_identities (user_id);
+
+-- =========================
+-- oidc_login_states (pending authorization requests)
+-- =========================
+CREATE TABLE IF NOT EXISTS oidc_login_states (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider VARCHAR(64) NOT NULL,
+    state_hash VARCHAR(64) NOT NULL UNIQUE,     -- sha256 hex of the `state` parameter
+    code_verifier VARCHAR(128) NOT NULL,        -- PKCE, never leaves the server
+    nonce VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    consumed_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,9 @@
+-- =========================
+-- users.deleted_at (soft delete)
+-- =========================
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;   -- NULL while active, purged after the retention window
+
+CREATE INDEX IF NOT EXISTS idx_users_deleted_at
+    ON users (deleted_at)
+    WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,5 @@
+-- =========================
+-- users.version (optimistic concurrency, served as the ETag)
+-- =========================
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS version BIGINT NOT NULL DEFAULT 1;   -- bumped by every change to the representation
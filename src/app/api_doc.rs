@@ -1,9 +1,24 @@
 use utoipa::OpenApi;
 
+use crate::shared::error::{FieldError, ProblemDetails};
+
 #[derive(OpenApi)]
 #[openapi(
     nest(
-        (path = "/users", api = crate::domain::users::api_doc::UsersApi)
-    )
+        (path = "/auth", api = crate::domain::auth::api_doc::AuthApi),
+        (path = "/api-keys", api = crate::domain::api_keys::api_doc::ApiKeysApi),
+        (path = "/auth/2fa", api = crate::domain::two_factor::api_doc::TwoFactorApi),
+        (path = "/auth/oidc", api = crate::domain::oidc::api_doc::OidcApi),
+        (path = "/users", api = crate::domain::users::api_doc::UsersApi),
+        (path = "/users/{id}/roles", api = crate::domain::roles::api_doc::RolesApi),
+        (path = "/users/{id}/2fa", api = crate::domain::two_factor::api_doc::TwoFactorAdminApi),
+        (path = "/users/{id}/lockout", api = crate::domain::lockout::api_doc::LockoutApi),
+        (path = "/users/{id}/schools", api = crate::domain::memberships::api_doc::UserSchoolsApi),
+        (path = "/sessions", api = crate::domain::sessions::api_doc::SessionsApi),
+        (path = "/schools", api = crate::domain::schools::api_doc::SchoolsApi),
+        (path = "/schools/{id}/members", api = crate::domain::memberships::api_doc::SchoolMembersApi),
+        (path = "/products", api = crate::domain::products::api_doc::ProductsApi)
+    ),
+    components(schemas(ProblemDetails, FieldError))
 )]
 pub struct ApiDoc;
@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use dotenvy::dotenv;
+use jsonwebtoken::Algorithm;
 use serde::Deserialize;
 
 use super::error::ConfigError;
@@ -7,6 +10,14 @@ use super::error::ConfigError;
 pub struct AppConfig {
     pub app: App,
     pub database: Database,
+    pub auth: Auth,
+    pub mail: Mail,
+    pub password_policy: PasswordPolicy,
+    pub password_hashing: PasswordHashing,
+    pub login_throttle: LoginThrottle,
+    pub session: Session,
+    pub oidc: Oidc,
+    pub user_retention: UserRetention,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -14,6 +25,15 @@ pub struct App {
     pub host: String,
     pub port: u16,
     pub env: String,
+    /// Locales tried after the client's preferences, e.g. `th,en`
+    pub locale_fallback: Vec<String>,
+    /// Locales every localized field must provide, e.g. `en`
+    pub required_locales: Vec<String>,
+    /// Take the client IP from `X-Forwarded-For`; only enable behind a trusted proxy
+    pub trust_proxy_headers: bool,
+    /// Trusted proxies in front of the app, each appending one
+    /// `X-Forwarded-For` entry; the client is that many entries from the right
+    pub trusted_proxy_hops: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -23,6 +43,225 @@ pub struct Database {
     pub connect_timeout_secs: u64,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct Auth {
+    pub jwt_algorithm: JwtAlgorithm,
+    /// Shared secret, required for HS256
+    pub jwt_secret: Option<String>,
+    /// PEM encoded keys, required for RS256
+    pub jwt_private_key: Option<String>,
+    pub jwt_public_key: Option<String>,
+    pub jwt_issuer: String,
+    pub access_token_ttl_secs: i64,
+    pub refresh_token_ttl_secs: i64,
+    /// Account granted `admin` on startup
+    pub bootstrap_admin_email: Option<String>,
+    pub password_reset_ttl_secs: i64,
+    /// Page that accepts the emailed token, e.g. `https://app.example.com/reset-password`
+    pub password_reset_url: String,
+    /// Refuse to log in accounts whose email hasn't been verified
+    pub require_verified_email: bool,
+    pub email_verification_ttl_secs: i64,
+    /// Page that accepts the emailed token, e.g. `https://app.example.com/verify-email`
+    pub email_verification_url: String,
+    /// Issuer shown by authenticator apps
+    pub totp_issuer: String,
+    pub two_factor_challenge_ttl_secs: i64,
+    /// Roles that may not use guarded endpoints until 2FA is enabled
+    pub two_factor_required_roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Mail {
+    pub from: String,
+    /// Directory the default file mailer writes `.eml` files to
+    pub dir: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Upper bound keeps argon2 hashing cost predictable
+    pub max_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    /// Reject passwords on the bundled common-password list
+    pub reject_common: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PasswordHashing {
+    pub variant: Argon2Variant,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// Server-side secret mixed into every hash, never stored in the database
+    pub pepper: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoginThrottle {
+    /// Failed logins for one account before it is locked
+    pub max_failures_per_account: i32,
+    /// Failed logins from one IP, across accounts, before it is blocked
+    pub max_failures_per_ip: i32,
+    /// Failures older than this no longer count
+    pub failure_window_secs: i64,
+    /// First lockout; each further one doubles it
+    pub lockout_base_secs: i64,
+    pub lockout_max_secs: i64,
+    /// Quiet period after which the backoff starts over
+    pub lockout_reset_secs: i64,
+    /// How often stale rows are purged; `0` disables it
+    pub purge_interval_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Session {
+    /// Allow `/auth/login` to start cookie sessions
+    pub enabled: bool,
+    pub cookie_name: String,
+    /// Send cookies over HTTPS only; disable for local HTTP development
+    pub cookie_secure: bool,
+    pub same_site: CookieSameSite,
+    /// Sessions unused for this long are expired
+    pub idle_timeout_secs: i64,
+    /// Sessions end this long after login regardless of activity
+    pub absolute_timeout_secs: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Oidc {
+    pub providers: Vec<OidcProvider>,
+    /// Register the in-process `mock` provider; only allowed with `APP_ENV=local` or `test`
+    pub mock_enabled: bool,
+    /// Where the `mock` provider sends the browser back to
+    pub mock_redirect_uri: String,
+    /// How long an authorization request may take to come back
+    pub state_ttl_secs: i64,
+    /// How often used and expired login states are purged; `0` disables it
+    pub state_purge_interval_secs: u64,
+    /// Whole-request limit for discovery, JWKS and token endpoint calls
+    pub http_timeout_secs: u64,
+    pub http_connect_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserRetention {
+    /// Soft-deleted users are purged this long after deletion
+    pub purge_after_secs: i64,
+    /// How often the purge job runs; `0` disables it
+    pub purge_interval_secs: u64,
+}
+
+/// One identity provider, read from `OIDC_<NAME>_*`
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcProvider {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    /// Page of the UI that receives `code` and `state`
+    pub redirect_uri: String,
+    pub scopes: String,
+    /// ID token signing algorithms accepted, e.g. `RS256,ES256`; HMAC is refused
+    pub algorithms: Vec<Algorithm>,
+}
+
+impl OidcProvider {
+    fn load(name: &str) -> Result<Self, ConfigError> {
+        let var = |suffix: &str| format!("OIDC_{}_{}", name.to_ascii_uppercase(), suffix);
+
+        Ok(OidcProvider {
+            name: name.to_string(),
+            issuer: get(&var("ISSUER"))?,
+            client_id: get(&var("CLIENT_ID"))?,
+            client_secret: get(&var("CLIENT_SECRET")).ok(),
+            authorization_endpoint: get(&var("AUTHORIZATION_ENDPOINT"))?,
+            token_endpoint: get(&var("TOKEN_ENDPOINT"))?,
+            jwks_uri: get(&var("JWKS_URI"))?,
+            redirect_uri: get(&var("REDIRECT_URI"))?,
+            scopes: get(&var("SCOPES")).unwrap_or("openid email profile".into()),
+            algorithms: get(&var("ALGORITHMS"))
+                .unwrap_or("RS256".into())
+                .split(',')
+                .map(str::trim)
+                .filter(|alg| !alg.is_empty())
+                .map(|alg| match Algorithm::from_str(alg) {
+                    Ok(Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) | Err(_) => {
+                        Err(ConfigError::Invalid(var("ALGORITHMS"), alg.to_string()))
+                    }
+                    Ok(alg) => Ok(alg),
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+}
+
+impl FromStr for CookieSameSite {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Ok(CookieSameSite::Strict),
+            "lax" => Ok(CookieSameSite::Lax),
+            other => Err(ConfigError::Invalid(
+                "SESSION_COOKIE_SAME_SITE".into(),
+                other.into(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Argon2Variant {
+    Argon2d,
+    Argon2i,
+    Argon2id,
+}
+
+impl FromStr for Argon2Variant {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "argon2d" => Ok(Argon2Variant::Argon2d),
+            "argon2i" => Ok(Argon2Variant::Argon2i),
+            "argon2id" => Ok(Argon2Variant::Argon2id),
+            other => Err(ConfigError::Invalid("ARGON2_VARIANT".into(), other.into())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum JwtAlgorithm {
+    HS256,
+    RS256,
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::HS256),
+            "RS256" => Ok(JwtAlgorithm::RS256),
+            other => Err(ConfigError::Invalid("JWT_ALGORITHM".into(), other.into())),
+        }
+    }
+}
+
 impl AppConfig {
     pub fn load() -> Result<Self, ConfigError> {
         dotenv().ok();
@@ -32,14 +271,177 @@ impl AppConfig {
                 host: get("APP_HOST")?,
                 port: get("APP_PORT")?.parse()?,
                 env: get("APP_ENV")?,
+                locale_fallback: get("LOCALE_FALLBACK")
+                    .unwrap_or("en".into())
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+                required_locales: get("REQUIRED_LOCALES")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+                trust_proxy_headers: flag("TRUST_PROXY_HEADERS", false)?,
+                trusted_proxy_hops: get("TRUSTED_PROXY_HOPS").unwrap_or("1".into()).parse()?,
             },
             database: Database {
                 url: get("DATABASE_URL")?,
                 max_connections: get("DB_MAX_CONNECTIONS").unwrap_or("10".into()).parse()?,
                 connect_timeout_secs: get("DB_CONNECT_TIMEOUT").unwrap_or("5".into()).parse()?,
             },
+            auth: Auth {
+                jwt_algorithm: get("JWT_ALGORITHM").unwrap_or("HS256".into()).parse()?,
+                jwt_secret: get("JWT_SECRET").ok(),
+                jwt_private_key: read_file("JWT_PRIVATE_KEY_PATH")?,
+                jwt_public_key: read_file("JWT_PUBLIC_KEY_PATH")?,
+                jwt_issuer: get("JWT_ISSUER").unwrap_or("crud-rust".into()),
+                access_token_ttl_secs: get("ACCESS_TOKEN_TTL_SECS")
+                    .unwrap_or("900".into())
+                    .parse()?,
+                refresh_token_ttl_secs: get("REFRESH_TOKEN_TTL_SECS")
+                    .unwrap_or("1209600".into())
+                    .parse()?,
+                bootstrap_admin_email: get("BOOTSTRAP_ADMIN_EMAIL").ok(),
+                password_reset_ttl_secs: get("PASSWORD_RESET_TTL_SECS")
+                    .unwrap_or("3600".into())
+                    .parse()?,
+                password_reset_url: get("PASSWORD_RESET_URL")
+                    .unwrap_or("http://localhost:3000/reset-password".into()),
+                require_verified_email: flag("REQUIRE_VERIFIED_EMAIL", false)?,
+                email_verification_ttl_secs: get("EMAIL_VERIFICATION_TTL_SECS")
+                    .unwrap_or("86400".into())
+                    .parse()?,
+                email_verification_url: get("EMAIL_VERIFICATION_URL")
+                    .unwrap_or("http://localhost:3000/verify-email".into()),
+                totp_issuer: get("TOTP_ISSUER").unwrap_or("crud-rust".into()),
+                two_factor_challenge_ttl_secs: get("TWO_FACTOR_CHALLENGE_TTL_SECS")
+                    .unwrap_or("300".into())
+                    .parse()?,
+                two_factor_required_roles: get("TWO_FACTOR_REQUIRED_ROLES")
+                    .unwrap_or("admin".into())
+                    .split(',')
+                    .map(|role| role.trim().to_string())
+                    .filter(|role| !role.is_empty())
+                    .collect(),
+            },
+            mail: Mail {
+                from: get("MAIL_FROM").unwrap_or("no-reply@localhost".into()),
+                dir: get("MAIL_DIR").unwrap_or("mail".into()),
+            },
+            password_policy: PasswordPolicy {
+                min_length: get("PASSWORD_MIN_LENGTH").unwrap_or("12".into()).parse()?,
+                max_length: get("PASSWORD_MAX_LENGTH").unwrap_or("128".into()).parse()?,
+                require_lowercase: flag("PASSWORD_REQUIRE_LOWERCASE", false)?,
+                require_uppercase: flag("PASSWORD_REQUIRE_UPPERCASE", false)?,
+                require_digit: flag("PASSWORD_REQUIRE_DIGIT", false)?,
+                require_symbol: flag("PASSWORD_REQUIRE_SYMBOL", false)?,
+                reject_common: flag("PASSWORD_REJECT_COMMON", true)?,
+            },
+            // defaults match OWASP's argon2id recommendation (and `Argon2::default()`)
+            password_hashing: PasswordHashing {
+                variant: get("ARGON2_VARIANT").unwrap_or("argon2id".into()).parse()?,
+                memory_kib: get("ARGON2_MEMORY_KIB").unwrap_or("19456".into()).parse()?,
+                iterations: get("ARGON2_ITERATIONS").unwrap_or("2".into()).parse()?,
+                parallelism: get("ARGON2_PARALLELISM").unwrap_or("1".into()).parse()?,
+                pepper: get("PASSWORD_PEPPER").ok().filter(|p| !p.is_empty()),
+            },
+            login_throttle: LoginThrottle {
+                max_failures_per_account: get("LOGIN_MAX_FAILURES")
+                    .unwrap_or("5".into())
+                    .parse()?,
+                max_failures_per_ip: get("LOGIN_MAX_FAILURES_PER_IP")
+                    .unwrap_or("20".into())
+                    .parse()?,
+                failure_window_secs: get("LOGIN_FAILURE_WINDOW_SECS")
+                    .unwrap_or("900".into())
+                    .parse()?,
+                lockout_base_secs: get("LOCKOUT_BASE_SECS").unwrap_or("60".into()).parse()?,
+                lockout_max_secs: get("LOCKOUT_MAX_SECS").unwrap_or("3600".into()).parse()?,
+                lockout_reset_secs: get("LOCKOUT_RESET_SECS")
+                    .unwrap_or("86400".into())
+                    .parse()?,
+                purge_interval_secs: get("LOGIN_THROTTLE_PURGE_INTERVAL_SECS")
+                    .unwrap_or("3600".into())
+                    .parse()?,
+            },
+            session: Session {
+                enabled: flag("SESSIONS_ENABLED", false)?,
+                cookie_name: get("SESSION_COOKIE_NAME").unwrap_or("session".into()),
+                cookie_secure: flag("SESSION_COOKIE_SECURE", true)?,
+                same_site: get("SESSION_COOKIE_SAME_SITE")
+                    .unwrap_or("strict".into())
+                    .parse()?,
+                idle_timeout_secs: get("SESSION_IDLE_TIMEOUT_SECS")
+                    .unwrap_or("1800".into())
+                    .parse()?,
+                absolute_timeout_secs: get("SESSION_ABSOLUTE_TIMEOUT_SECS")
+                    .unwrap_or("43200".into())
+                    .parse()?,
+            },
+            oidc: Oidc {
+                providers: get("OIDC_PROVIDERS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(OidcProvider::load)
+                    .collect::<Result<_, _>>()?,
+                mock_enabled: flag("OIDC_MOCK_ENABLED", false)?,
+                mock_redirect_uri: get("OIDC_MOCK_REDIRECT_URI")
+                    .unwrap_or("http://localhost:3000/oidc/callback".into()),
+                state_ttl_secs: get("OIDC_STATE_TTL_SECS").unwrap_or("600".into()).parse()?,
+                state_purge_interval_secs: get("OIDC_STATE_PURGE_INTERVAL_SECS")
+                    .unwrap_or("3600".into())
+                    .parse()?,
+                http_timeout_secs: get("OIDC_HTTP_TIMEOUT_SECS")
+                    .unwrap_or("10".into())
+                    .parse()?,
+                http_connect_timeout_secs: get("OIDC_HTTP_CONNECT_TIMEOUT_SECS")
+                    .unwrap_or("5".into())
+                    .parse()?,
+            },
+            user_retention: UserRetention {
+                // 30 days
+                purge_after_secs: get("USER_PURGE_AFTER_SECS")
+                    .unwrap_or("2592000".into())
+                    .parse()?,
+                purge_interval_secs: get("USER_PURGE_INTERVAL_SECS")
+                    .unwrap_or("3600".into())
+                    .parse()?,
+            },
         };
 
+        // fail here rather than on the first token issued
+        match config.auth.jwt_algorithm {
+            JwtAlgorithm::HS256
+                if config
+                    .auth
+                    .jwt_secret
+                    .as_deref()
+                    .unwrap_or_default()
+                    .is_empty() =>
+            {
+                return Err(ConfigError::MissingVar("JWT_SECRET".into()));
+            }
+            JwtAlgorithm::RS256 if config.auth.jwt_private_key.is_none() => {
+                return Err(ConfigError::MissingVar("JWT_PRIVATE_KEY_PATH".into()));
+            }
+            JwtAlgorithm::RS256 if config.auth.jwt_public_key.is_none() => {
+                return Err(ConfigError::MissingVar("JWT_PUBLIC_KEY_PATH".into()));
+            }
+            _ => {}
+        }
+
+        // the mock signs anyone in as any email, admins included
+        if config.oidc.mock_enabled && !matches!(config.app.env.as_str(), "local" | "test") {
+            return Err(ConfigError::Invalid(
+                "OIDC_MOCK_ENABLED".into(),
+                format!("not allowed with APP_ENV={}", config.app.env),
+            ));
+        }
+
         Ok(config)
     }
 }
@@ -47,3 +449,23 @@ impl AppConfig {
 fn get(key: &str) -> Result<String, ConfigError> {
     std::env::var(key).map_err(|_| ConfigError::MissingVar(key.to_string()))
 }
+
+/// `true`/`false` (also `1`/`0`), `default` when unset
+fn flag(key: &str, default: bool) -> Result<bool, ConfigError> {
+    match get(key) {
+        Ok(value) => match value.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(ConfigError::Invalid(key.to_string(), value)),
+        },
+        Err(_) => Ok(default),
+    }
+}
+
+/// Reads the file referenced by `key`, if the variable is set
+fn read_file(key: &str) -> Result<Option<String>, ConfigError> {
+    match get(key) {
+        Ok(path) => Ok(Some(std::fs::read_to_string(path)?)),
+        Err(_) => Ok(None),
+    }
+}
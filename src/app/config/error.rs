@@ -8,6 +8,12 @@ pub enum ConfigError {
     #[error("invalid value: {0}")]
     InvalidValue(#[from] std::num::ParseIntError),
 
+    #[error("invalid value for `{0}`: {1}")]
+    Invalid(String, String),
+
     #[error("env error: {0}")]
     Env(#[from] std::env::VarError),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
 }
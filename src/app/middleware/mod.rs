@@ -1 +1,2 @@
+pub mod problem;
 pub mod trace;
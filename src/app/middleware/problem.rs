@@ -0,0 +1,31 @@
+use axum::{
+    body::Body, extract::Request, http::header::CONTENT_LENGTH, middleware::Next,
+    response::Response,
+};
+
+use crate::shared::error::ProblemDetails;
+
+/// Fills `instance` on problem+json responses with the request path
+pub async fn problem_instance(req: Request, next: Next) -> Response {
+    let path = req.uri().path().to_string();
+    let response = next.run(req).await;
+
+    let Some(problem) = response.extensions().get::<ProblemDetails>() else {
+        return response;
+    };
+    if problem.instance.is_some() {
+        return response;
+    }
+
+    let mut problem = problem.clone();
+    problem.instance = Some(path);
+
+    let Ok(body) = serde_json::to_vec(&problem) else {
+        return response;
+    };
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(CONTENT_LENGTH);
+    parts.extensions.insert(problem);
+    Response::from_parts(parts, Body::from(body))
+}
@@ -1,13 +1,58 @@
 use crate::app::api_doc::ApiDoc;
-use crate::app::middleware::trace;
+use crate::app::middleware::{problem, trace};
 use crate::app::state::AppState;
-use axum::Router;
+use crate::shared::error::AppError;
+use axum::{Router, middleware};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-pub fn router() -> Router<AppState> {
-    Router::new()
-        .nest("/users", crate::domain::users::routes::router())
+pub fn router(state: &AppState) -> Router<AppState> {
+    let mut router = Router::new()
+        .nest("/auth", crate::domain::auth::routes::router())
+        .nest("/api-keys", crate::domain::api_keys::routes::router())
+        .nest("/auth/2fa", crate::domain::two_factor::routes::router())
+        .nest("/auth/oidc", crate::domain::oidc::routes::router())
+        .nest("/users", crate::domain::users::routes::router(state))
+        .nest(
+            "/users/{id}/roles",
+            crate::domain::roles::routes::router(state),
+        )
+        .nest(
+            "/users/{id}/2fa",
+            crate::domain::two_factor::routes::admin_router(state),
+        )
+        .nest(
+            "/users/{id}/lockout",
+            crate::domain::lockout::routes::router(state),
+        )
+        .nest(
+            "/users/{id}/schools",
+            crate::domain::memberships::routes::user_schools_router(),
+        )
+        .nest("/sessions", crate::domain::sessions::routes::router())
+        .nest("/schools", crate::domain::schools::routes::router(state))
+        .nest(
+            "/schools/{id}/members",
+            crate::domain::memberships::routes::school_members_router(),
+        )
+        .nest("/products", crate::domain::products::routes::router(state));
+
+    if state.oidc.mock().is_some() {
+        router = router.nest("/mock-oidc", crate::domain::oidc::routes::mock_router());
+    }
+
+    router
+        .fallback(route_not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .layer(middleware::from_fn(problem::problem_instance))
         .layer(trace::global_trace_layer())
         .merge(SwaggerUi::new("/swagger").url("/api-doc/openapi.json", ApiDoc::openapi()))
 }
+
+async fn route_not_found() -> AppError {
+    AppError::not_found("Route not found")
+}
+
+async fn method_not_allowed() -> AppError {
+    AppError::MethodNotAllowed
+}
@@ -1,8 +1,18 @@
+use std::sync::Arc;
+
 use crate::app::config::config::AppConfig;
+use crate::infra::mailer::Mailer;
+use crate::infra::oidc::IdentityProviders;
+use crate::shared::security::jwt::JwtKeys;
+use crate::shared::security::password::PasswordHasher;
 use sqlx::PgPool;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub config: AppConfig,
+    pub jwt: JwtKeys,
+    pub passwords: PasswordHasher,
+    pub mailer: Arc<dyn Mailer>,
+    pub oidc: IdentityProviders,
 }
@@ -0,0 +1,15 @@
+use utoipa::OpenApi;
+
+use super::dtos::create::CreateApiKeyRequest;
+use super::dtos::response::{ApiKeyResponse, CreatedApiKeyResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::create_api_key,
+        super::handlers::list_api_keys,
+        super::handlers::revoke_api_key
+    ),
+    components(schemas(CreateApiKeyRequest, ApiKeyResponse, CreatedApiKeyResponse))
+)]
+pub struct ApiKeysApi;
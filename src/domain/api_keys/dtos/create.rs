@@ -0,0 +1,38 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::roles::entities::Permission;
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// What the key is for, e.g. `nightly export`
+    pub name: String,
+    /// Permissions the key may use, e.g. `users:read`
+    pub scopes: Vec<String>,
+    /// UTC; omit for a key that never expires
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl Validate for CreateApiKeyRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name)
+            .length("name", &self.name, 0, 100);
+
+        if self.scopes.is_empty() {
+            v.add("scopes", "required", "must list at least one scope");
+        }
+        for (i, scope) in self.scopes.iter().enumerate() {
+            if let Err(message) = scope.parse::<Permission>() {
+                v.add(&format!("scopes[{}]", i), "unknown_scope", message);
+            }
+        }
+
+        if let Some(expires_at) = self.expires_at
+            && expires_at <= Utc::now().naive_utc()
+        {
+            v.add("expires_at", "in_past", "must be in the future");
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::dtos::create::CreateApiKeyRequest;
+use super::dtos::response::{ApiKeyResponse, CreatedApiKeyResponse};
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ValidatedJson, ValidatedPath};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Create an API key, the key is only shown once", body = CreatedApiKeyResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "API keys cannot manage API keys"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(req): ValidatedJson<CreateApiKeyRequest>,
+) -> Result<ApiResponse<CreatedApiKeyResponse>, AppError> {
+    require_interactive(&user)?;
+
+    match usecases::create_api_key(&state.db, user.id, req).await {
+        DomainResult::Ok((api_key, key)) => Ok(ApiResponse::created(CreatedApiKeyResponse {
+            key,
+            api_key: api_key.into(),
+        })),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses(
+        (status = 200, description = "List the caller's API keys", body = [ApiKeyResponse]),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "API keys cannot manage API keys")
+    )
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<ApiResponse<Vec<ApiKeyResponse>>, AppError> {
+    require_interactive(&user)?;
+
+    match usecases::list_api_keys(&state.db, user.id).await {
+        DomainResult::Ok(keys) => Ok(ApiResponse::ok(
+            keys.into_iter().map(ApiKeyResponse::from).collect(),
+        )),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{key_id}",
+    responses(
+        (status = 200, description = "Revoke an API key"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "API keys cannot manage API keys"),
+        (status = 404, description = "API key not found or already revoked")
+    ),
+    params(
+        ("key_id" = Uuid, Path, description = "API key ID")
+    )
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath(key_id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    require_interactive(&user)?;
+
+    match usecases::revoke_api_key(&state.db, user.id, key_id).await {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("API key not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+/// A leaked key must not be able to mint or keep alive other keys
+fn require_interactive(user: &AuthUser) -> Result<(), AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+    Ok(())
+}
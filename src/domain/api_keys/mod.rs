@@ -0,0 +1,6 @@
+pub mod api_doc;
+pub mod dtos;
+pub mod entities;
+pub mod handlers;
+pub mod routes;
+pub mod usecases;
@@ -0,0 +1,14 @@
+use axum::Router;
+use axum::routing::{delete, get};
+
+use super::handlers;
+use crate::app::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::list_api_keys).post(handlers::create_api_key),
+        )
+        .route("/{key_id}", delete(handlers::revoke_api_key))
+}
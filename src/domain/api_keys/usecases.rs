@@ -0,0 +1,133 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::dtos::create::CreateApiKeyRequest;
+use super::entities::api_key_entity::ApiKeyEntity;
+use crate::domain::auth::extractors::AuthUser;
+use crate::shared::db_error::DbError;
+use crate::shared::security::token::{generate_token, hash_token};
+use crate::shared::types::result::DomainResult;
+
+/// Marks the string as one of our keys, e.g. for secret scanners
+const KEY_PREFIX: &str = "ck_";
+/// Characters of the key kept in clear for display
+const VISIBLE_PREFIX_LEN: usize = 11;
+
+/// =========================
+/// CREATE API KEY
+/// =========================
+/// Returns the stored key together with the raw key, which is not
+/// retrievable afterwards.
+pub async fn create_api_key(
+    pool: &PgPool,
+    user_id: Uuid,
+    req: CreateApiKeyRequest,
+) -> DomainResult<(ApiKeyEntity, String), DbError> {
+    let key = format!("{}{}", KEY_PREFIX, generate_token());
+
+    let created = sqlx::query_as::<_, ApiKeyEntity>(
+        r#"
+        INSERT INTO api_keys (user_id, name, prefix, key_hash, scopes, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, user_id, name, prefix, scopes, expires_at, last_used_at, revoked_at, created_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(req.name.trim())
+    .bind(&key[..VISIBLE_PREFIX_LEN])
+    .bind(hash_token(&key))
+    .bind(&req.scopes)
+    .bind(req.expires_at)
+    .fetch_one(pool)
+    .await;
+
+    match created {
+        Ok(k) => DomainResult::Ok((k, key)),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST API KEYS
+/// =========================
+pub async fn list_api_keys(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> DomainResult<Vec<ApiKeyEntity>, DbError> {
+    let keys = sqlx::query_as::<_, ApiKeyEntity>(
+        r#"
+        SELECT id, user_id, name, prefix, scopes, expires_at, last_used_at, revoked_at, created_at
+        FROM api_keys
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await;
+
+    match keys {
+        Ok(k) => DomainResult::Ok(k),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// REVOKE API KEY
+/// =========================
+/// Keys of other users and already revoked keys yield `NotFound`
+pub async fn revoke_api_key(pool: &PgPool, user_id: Uuid, id: Uuid) -> DomainResult<(), DbError> {
+    let res = sqlx::query(
+        r#"
+        UPDATE api_keys
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .bind(user_id)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// AUTHENTICATE
+/// =========================
+/// Resolves a live key to its owner and records the use. Unknown,
+/// revoked and expired keys yield `NotFound`.
+pub async fn authenticate(pool: &PgPool, key: &str) -> DomainResult<AuthUser, DbError> {
+    let owner = sqlx::query_as::<_, (Uuid, String, Vec<String>)>(
+        r#"
+        UPDATE api_keys k
+        SET last_used_at = CURRENT_TIMESTAMP
+        FROM users u
+        WHERE u.id = k.user_id
+          AND u.deleted_at IS NULL
+          AND k.key_hash = $1
+          AND k.revoked_at IS NULL
+          AND (k.expires_at IS NULL OR k.expires_at > CURRENT_TIMESTAMP)
+        RETURNING u.id, u.email, k.scopes
+        "#,
+    )
+    .bind(hash_token(key))
+    .fetch_optional(pool)
+    .await;
+
+    match owner {
+        Ok(Some((id, email, scopes))) => DomainResult::Ok(AuthUser {
+            id,
+            email,
+            // scopes are validated on creation; skip any retired since
+            scopes: Some(scopes.iter().filter_map(|s| s.parse().ok()).collect()),
+            session_id: None,
+        }),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
@@ -0,0 +1,31 @@
+use utoipa::OpenApi;
+
+use super::dtos::login::{LoginRequest, LoginResponse};
+use super::dtos::password::{ForgotPasswordRequest, ResetPasswordRequest};
+use super::dtos::refresh::RefreshRequest;
+use super::dtos::token::TokenResponse;
+use super::dtos::verify_email::{ResendVerificationRequest, VerifyEmailRequest};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::login,
+        super::handlers::refresh,
+        super::handlers::logout,
+        super::handlers::forgot_password,
+        super::handlers::reset_password,
+        super::handlers::verify_email,
+        super::handlers::resend_verification
+    ),
+    components(schemas(
+        LoginRequest,
+        LoginResponse,
+        RefreshRequest,
+        TokenResponse,
+        ForgotPasswordRequest,
+        ResetPasswordRequest,
+        VerifyEmailRequest,
+        ResendVerificationRequest
+    ))
+)]
+pub struct AuthApi;
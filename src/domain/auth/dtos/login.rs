@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::token::TokenResponse;
+use crate::domain::sessions::dtos::response::SessionStartedResponse;
+use crate::domain::two_factor::dtos::challenge::TwoFactorChallengeResponse;
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+    /// Start a cookie session instead of issuing tokens
+    #[serde(default)]
+    pub session: bool,
+}
+
+impl Validate for LoginRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("email", &self.email)
+            .required("password", &self.password);
+    }
+}
+
+/// Tokens or a session, or a challenge when the account has two-factor
+/// authentication enabled
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Tokens(TokenResponse),
+    Session(SessionStartedResponse),
+    TwoFactorRequired(TwoFactorChallengeResponse),
+}
@@ -0,0 +1,5 @@
+pub mod login;
+pub mod password;
+pub mod refresh;
+pub mod token;
+pub mod verify_email;
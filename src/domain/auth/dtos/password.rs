@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+impl Validate for ForgotPasswordRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", &self.email);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    /// Token from the reset email
+    pub token: String,
+    pub new_password: String,
+}
+
+impl Validate for ResetPasswordRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("token", &self.token)
+            .required("new_password", &self.new_password);
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VerifyEmailRequest {
+    /// Token from the verification email
+    pub token: String,
+}
+
+impl Validate for VerifyEmailRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("token", &self.token);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResendVerificationRequest {
+    pub email: String,
+}
+
+impl Validate for ResendVerificationRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", &self.email);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod email_verification_token;
+pub mod password_reset_token;
+pub mod refresh_token;
@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::shared::db_error::DbError;
+use crate::shared::error::{AppError, FieldError};
+
+/// Failures of the auth usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum AuthError {
+    /// Stored password hash could not be parsed
+    Hashing(String),
+    /// New password rejected by the configured policy
+    WeakPassword(Vec<FieldError>),
+    /// Mail transport failed
+    Mail(String),
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for AuthError {
+    fn from(err: sqlx::Error) -> Self {
+        AuthError::Db(err.into())
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Hashing(msg) => write!(f, "password verification failed: {}", msg),
+            AuthError::WeakPassword(_) => write!(f, "password rejected by policy"),
+            AuthError::Mail(msg) => write!(f, "sending mail failed: {}", msg),
+            AuthError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<AuthError> for AppError {
+    fn from(err: AuthError) -> Self {
+        match err {
+            AuthError::Hashing(msg) | AuthError::Mail(msg) => AppError::internal_server_error(msg),
+            AuthError::WeakPassword(errors) => AppError::Validation(errors),
+            AuthError::Db(db) => db.into(),
+        }
+    }
+}
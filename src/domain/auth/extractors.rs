@@ -0,0 +1,140 @@
+use axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{StatusCode, header::AUTHORIZATION, request::Parts},
+};
+use axum_extra::extract::cookie::CookieJar;
+use uuid::Uuid;
+
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::{api_keys, sessions};
+use crate::shared::error::AppError;
+use crate::shared::security::token::{constant_time_eq, hash_token};
+use crate::shared::types::result::DomainResult;
+
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// Authenticated caller, resolved from an `Authorization: Bearer <jwt>`
+/// or `Authorization: ApiKey <key>` header, or else from the session
+/// cookie when sessions are enabled
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub email: String,
+    /// Permissions the API key was limited to, `None` otherwise
+    pub scopes: Option<Vec<Permission>>,
+    /// Set when authenticated by session cookie
+    pub session_id: Option<Uuid>,
+}
+
+impl AuthUser {
+    pub fn is_api_key(&self) -> bool {
+        self.scopes.is_some()
+    }
+
+    /// Whether the credential may exercise `permission`. This only narrows
+    /// what the user's roles grant, it never widens it.
+    pub fn allows(&self, permission: Permission) -> bool {
+        match &self.scopes {
+            Some(scopes) => scopes.contains(&permission),
+            None => true,
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let state = AppState::from_ref(state);
+
+        let Some(header) = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return session_user(parts, &state).await;
+        };
+
+        if let Some(key) = header.strip_prefix("ApiKey ") {
+            return match api_keys::usecases::authenticate(&state.db, key.trim()).await {
+                DomainResult::Ok(user) => Ok(user),
+                DomainResult::NotFound => Err(AppError::Unauthorized),
+                DomainResult::Err(e) => Err(e.into()),
+            };
+        }
+
+        let token = header
+            .strip_prefix("Bearer ")
+            .ok_or(AppError::Unauthorized)?;
+
+        let claims = state
+            .jwt
+            .verify(token.trim())
+            .map_err(|_| AppError::Unauthorized)?;
+
+        match usecases::is_active(&state.db, claims.sub).await {
+            DomainResult::Ok(true) => {}
+            DomainResult::Ok(false) => return Err(AppError::Unauthorized),
+            DomainResult::Err(e) => return Err(e.into()),
+            _ => {
+                return Err(AppError::internal_server_error(
+                    "Unexpected error".to_string(),
+                ));
+            }
+        }
+
+        Ok(AuthUser {
+            id: claims.sub,
+            email: claims.email,
+            scopes: None,
+            session_id: None,
+        })
+    }
+}
+
+/// Cookie authentication; state-changing requests must echo the session's
+/// CSRF token in `X-CSRF-Token`, which a cross-site form cannot do
+async fn session_user(parts: &Parts, state: &AppState) -> Result<AuthUser, AppError> {
+    let config = &state.config.session;
+    if !config.enabled {
+        return Err(AppError::Unauthorized);
+    }
+
+    let jar = CookieJar::from_headers(&parts.headers);
+    let token = jar
+        .get(&config.cookie_name)
+        .map(|cookie| cookie.value().to_string())
+        .ok_or(AppError::Unauthorized)?;
+
+    let (user, csrf_hash) =
+        match sessions::usecases::authenticate(&state.db, &token, config.idle_timeout_secs).await {
+            DomainResult::Ok(found) => found,
+            DomainResult::NotFound => return Err(AppError::Unauthorized),
+            DomainResult::Err(e) => return Err(e.into()),
+        };
+
+    if !parts.method.is_safe() {
+        let presented = parts
+            .headers
+            .get(CSRF_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(hash_token)
+            .unwrap_or_default();
+
+        if !constant_time_eq(presented.as_bytes(), csrf_hash.as_bytes()) {
+            return Err(AppError::problem(
+                StatusCode::FORBIDDEN,
+                "csrf_token_invalid",
+                "Missing or invalid X-CSRF-Token header",
+            ));
+        }
+    }
+
+    Ok(user)
+}
@@ -0,0 +1,460 @@
+use axum::extract::State;
+use axum::http::header::USER_AGENT;
+use axum::http::{HeaderMap, StatusCode};
+use axum_extra::extract::cookie::CookieJar;
+use tracing::error;
+use uuid::Uuid;
+
+use super::dtos::login::{LoginRequest, LoginResponse};
+use super::dtos::password::{ForgotPasswordRequest, ResetPasswordRequest};
+use super::dtos::refresh::RefreshRequest;
+use super::dtos::token::TokenResponse;
+use super::dtos::verify_email::{ResendVerificationRequest, VerifyEmailRequest};
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::lockout;
+use crate::domain::lockout::entities::ThrottleScope;
+use crate::domain::sessions;
+use crate::domain::sessions::dtos::response::SessionStartedResponse;
+use crate::domain::two_factor;
+use crate::domain::two_factor::dtos::challenge::TwoFactorChallengeResponse;
+use crate::domain::users::entities::user_entity::UserEntity;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ClientIp, ValidatedJson};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    post,
+    path = "/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Issue tokens or start a cookie session, or a two-factor challenge when enabled", body = LoginResponse),
+        (status = 400, description = "Cookie sessions are not enabled"),
+        (status = 401, description = "Invalid credentials"),
+        (status = 403, description = "Email address not verified yet"),
+        (status = 422, description = "Request body failed validation"),
+        (status = 429, description = "Account or client locked after repeated failures, see Retry-After")
+    )
+)]
+pub async fn login(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    ValidatedJson(req): ValidatedJson<LoginRequest>,
+) -> Result<(CookieJar, ApiResponse<LoginResponse>), AppError> {
+    if req.session && !state.config.session.enabled {
+        return Err(AppError::bad_request("Cookie sessions are not enabled"));
+    }
+
+    let account = lockout::usecases::account_key(&req.email);
+
+    // 1️⃣ refuse locked accounts and clients before touching the password
+    check_login_throttle(&state, Some(&account), ip.as_deref()).await?;
+
+    // 2️⃣ check the password, counting failures
+    let user = match usecases::authenticate(&state.db, &state.passwords, &req).await {
+        DomainResult::Ok(user) => user,
+        DomainResult::NotFound => {
+            return Err(record_login_failure(
+                &state,
+                Some(&account),
+                ip.as_deref(),
+                AppError::Unauthorized,
+            )
+            .await);
+        }
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    // checked only after the password, so it doesn't reveal which accounts exist
+    if state.config.auth.require_verified_email && user.verified_at.is_none() {
+        return Err(AppError::problem(
+            StatusCode::FORBIDDEN,
+            "email_not_verified",
+            "Email address has not been verified",
+        ));
+    }
+
+    // tokens are withheld until the second factor is verified
+    if let Some(challenge) = two_factor_challenge(&state, user.id).await? {
+        return Ok((CookieJar::new(), ApiResponse::ok(challenge)));
+    }
+
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    let (jar, response) =
+        complete_login(&state, &user, req.session, user_agent, ip.as_deref()).await?;
+
+    Ok((jar, ApiResponse::ok(response)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Rotate refresh token and issue new access token", body = TokenResponse),
+        (status = 401, description = "Refresh token invalid, expired or reused"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn refresh(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<RefreshRequest>,
+) -> Result<ApiResponse<TokenResponse>, AppError> {
+    let (user, refresh_token) = match usecases::rotate_refresh_token(
+        &state.db,
+        &req.refresh_token,
+        state.config.auth.refresh_token_ttl_secs,
+    )
+    .await
+    {
+        DomainResult::Ok(rotated) => rotated,
+        DomainResult::NotFound => return Err(AppError::Unauthorized),
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    let access_token = state.jwt.issue(user.id, &user.email)?;
+
+    Ok(ApiResponse::ok(TokenResponse::bearer(
+        access_token,
+        refresh_token,
+        state.jwt.ttl_secs(),
+    )))
+}
+
+#[utoipa::path(
+    post,
+    path = "/password/forgot",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 202, description = "Reset email sent if the account exists"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn forgot_password(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<ForgotPasswordRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    // lookup and delivery run detached so neither the response nor its
+    // timing reveals whether the email is registered
+    tokio::spawn(async move {
+        let result = usecases::request_password_reset(
+            &state.db,
+            state.mailer.as_ref(),
+            &req.email,
+            state.config.auth.password_reset_ttl_secs,
+            &state.config.auth.password_reset_url,
+        )
+        .await;
+
+        if let DomainResult::Err(e) = result {
+            error!(error = %e, "password reset request failed");
+        }
+    });
+
+    Ok(ApiResponse::accepted())
+}
+
+#[utoipa::path(
+    post,
+    path = "/password/reset",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password changed, all sessions signed out"),
+        (status = 400, description = "Reset token invalid, expired or already used"),
+        (status = 422, description = "Request body failed validation or password policy")
+    )
+)]
+pub async fn reset_password(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<ResetPasswordRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::reset_password(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        &req.token,
+        &req.new_password,
+    )
+    .await
+    {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::bad_request("Invalid or expired reset token")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/verify-email",
+    request_body = VerifyEmailRequest,
+    responses(
+        (status = 200, description = "Email address verified"),
+        (status = 400, description = "Verification token invalid, expired or already used"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<VerifyEmailRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::verify_email(&state.db, &req.token).await {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::bad_request(
+            "Invalid or expired verification token",
+        )),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/verify-email/resend",
+    request_body = ResendVerificationRequest,
+    responses(
+        (status = 202, description = "Verification email sent if the account is unverified"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn resend_verification(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<ResendVerificationRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    // detached for the same reason as `forgot_password`
+    tokio::spawn(async move {
+        let result = usecases::resend_email_verification(
+            &state.db,
+            state.mailer.as_ref(),
+            &req.email,
+            state.config.auth.email_verification_ttl_secs,
+            &state.config.auth.email_verification_url,
+        )
+        .await;
+
+        if let DomainResult::Err(e) = result {
+            error!(error = %e, "resending email verification failed");
+        }
+    });
+
+    Ok(ApiResponse::accepted())
+}
+
+#[utoipa::path(
+    post,
+    path = "/logout",
+    responses(
+        (status = 200, description = "End the current cookie session and clear its cookies"),
+        (status = 400, description = "Not signed in with a session cookie"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Missing or invalid CSRF token")
+    )
+)]
+pub async fn logout(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<(CookieJar, ApiResponse<()>), AppError> {
+    let Some(session_id) = user.session_id else {
+        return Err(AppError::bad_request("Not signed in with a session cookie"));
+    };
+
+    match sessions::usecases::revoke_session(&state.db, user.id, session_id).await {
+        DomainResult::Ok(()) | DomainResult::NotFound => Ok((
+            sessions::cookies::cleared_cookies(&state.config.session),
+            ApiResponse::ok_empty(),
+        )),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+/// A two-factor challenge to answer instead of tokens, if the user has
+/// 2FA enabled
+pub async fn two_factor_challenge(
+    state: &AppState,
+    user_id: Uuid,
+) -> Result<Option<LoginResponse>, AppError> {
+    match two_factor::usecases::is_enabled(&state.db, user_id).await {
+        DomainResult::Ok(false) => return Ok(None),
+        DomainResult::Ok(true) => {}
+        DomainResult::Err(e) => return Err(e.into()),
+        _ => {
+            return Err(AppError::internal_server_error(
+                "Unexpected error".to_string(),
+            ));
+        }
+    }
+
+    let ttl_secs = state.config.auth.two_factor_challenge_ttl_secs;
+    match two_factor::usecases::create_challenge(&state.db, user_id, ttl_secs).await {
+        DomainResult::Ok(token) => Ok(Some(LoginResponse::TwoFactorRequired(
+            TwoFactorChallengeResponse::new(token, ttl_secs),
+        ))),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+/// Finish a login for `user`: a cookie session when `session` is set,
+/// otherwise an access and refresh token pair
+pub async fn complete_login(
+    state: &AppState,
+    user: &UserEntity,
+    session: bool,
+    user_agent: Option<&str>,
+    ip: Option<&str>,
+) -> Result<(CookieJar, LoginResponse), AppError> {
+    // only a fully successful login resets the account's backoff, never
+    // the IP's; clearing it on the password alone would hand out fresh
+    // challenges to brute-force the second factor with
+    let account = lockout::usecases::account_key(&user.email);
+    if let DomainResult::Err(e) =
+        lockout::usecases::clear(&state.db, ThrottleScope::Account, &account).await
+    {
+        return Err(e.into());
+    }
+
+    if !session {
+        let tokens = issue_tokens(state, user).await?;
+        return Ok((CookieJar::new(), LoginResponse::Tokens(tokens)));
+    }
+
+    let config = &state.config.session;
+    let started = match sessions::usecases::create_session(
+        &state.db,
+        user.id,
+        user_agent,
+        ip,
+        config.absolute_timeout_secs,
+    )
+    .await
+    {
+        DomainResult::Ok(started) => started,
+        DomainResult::Err(e) => return Err(e.into()),
+        _ => {
+            return Err(AppError::internal_server_error(
+                "Unexpected error".to_string(),
+            ));
+        }
+    };
+
+    let jar = sessions::cookies::session_cookies(config, started.token, started.csrf_token.clone());
+
+    Ok((
+        jar,
+        LoginResponse::Session(SessionStartedResponse {
+            csrf_token: started.csrf_token,
+            expires_in: config.absolute_timeout_secs,
+        }),
+    ))
+}
+
+/// Send a verification email without holding up (or failing) the request
+pub fn spawn_email_verification(state: AppState, user_id: Uuid, email: String) {
+    tokio::spawn(async move {
+        let result = usecases::send_email_verification(
+            &state.db,
+            state.mailer.as_ref(),
+            user_id,
+            &email,
+            state.config.auth.email_verification_ttl_secs,
+            &state.config.auth.email_verification_url,
+        )
+        .await;
+
+        if let DomainResult::Err(e) = result {
+            error!(user_id = %user_id, error = %e, "sending email verification failed");
+        }
+    });
+}
+
+/// Start a new refresh token family and sign an access token for `user`
+async fn issue_tokens(state: &AppState, user: &UserEntity) -> Result<TokenResponse, AppError> {
+    let refresh_token = match usecases::issue_refresh_token(
+        &state.db,
+        user.id,
+        state.config.auth.refresh_token_ttl_secs,
+    )
+    .await
+    {
+        DomainResult::Ok(token) => token,
+        DomainResult::Err(e) => return Err(e.into()),
+        _ => {
+            return Err(AppError::internal_server_error(
+                "Unexpected error".to_string(),
+            ));
+        }
+    };
+
+    let access_token = state.jwt.issue(user.id, &user.email)?;
+
+    Ok(TokenResponse::bearer(
+        access_token,
+        refresh_token,
+        state.jwt.ttl_secs(),
+    ))
+}
+
+/// Refuse with `429` while the account or the client is locked out
+pub async fn check_login_throttle(
+    state: &AppState,
+    account: Option<&str>,
+    ip: Option<&str>,
+) -> Result<(), AppError> {
+    let mut keys = Vec::new();
+    if let Some(account) = account {
+        keys.push((ThrottleScope::Account, account));
+    }
+    if let Some(ip) = ip {
+        keys.push((ThrottleScope::Ip, ip));
+    }
+
+    match lockout::usecases::retry_after(&state.db, &keys).await {
+        DomainResult::Ok(None) => Ok(()),
+        DomainResult::Ok(Some(secs)) => Err(AppError::TooManyRequests(secs)),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+/// Count a failed login against the account and the client, returning the
+/// error to respond with: `429` if this failure triggered a lock, else
+/// `rejection`.
+pub async fn record_login_failure(
+    state: &AppState,
+    account: Option<&str>,
+    ip: Option<&str>,
+    rejection: AppError,
+) -> AppError {
+    let policy = &state.config.login_throttle;
+
+    let mut targets = Vec::new();
+    if let Some(account) = account {
+        targets.push((
+            ThrottleScope::Account,
+            account,
+            policy.max_failures_per_account,
+        ));
+    }
+    if let Some(ip) = ip {
+        targets.push((ThrottleScope::Ip, ip, policy.max_failures_per_ip));
+    }
+
+    let mut locked_for = None;
+    for (scope, key, max_failures) in targets {
+        match lockout::usecases::record_failure(&state.db, policy, scope, key, max_failures).await {
+            DomainResult::Ok(lock) => locked_for = locked_for.max(lock),
+            DomainResult::Err(e) => return e.into(),
+            _ => return AppError::internal_server_error("Unexpected error".to_string()),
+        }
+    }
+
+    match locked_for {
+        Some(secs) => AppError::TooManyRequests(secs),
+        None => rejection,
+    }
+}
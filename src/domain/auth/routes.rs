@@ -0,0 +1,16 @@
+use axum::Router;
+use axum::routing::post;
+
+use super::handlers;
+use crate::app::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/login", post(handlers::login))
+        .route("/refresh", post(handlers::refresh))
+        .route("/logout", post(handlers::logout))
+        .route("/password/forgot", post(handlers::forgot_password))
+        .route("/password/reset", post(handlers::reset_password))
+        .route("/verify-email", post(handlers::verify_email))
+        .route("/verify-email/resend", post(handlers::resend_verification))
+}
@@ -0,0 +1,682 @@
+use sqlx::{PgConnection, PgPool};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::dtos::login::LoginRequest;
+use super::entities::email_verification_token::EmailVerificationTokenEntity;
+use super::entities::password_reset_token::PasswordResetTokenEntity;
+use super::entities::refresh_token::RefreshTokenEntity;
+use super::error::AuthError;
+use crate::app::config::config::PasswordPolicy;
+use crate::domain::users;
+use crate::domain::users::entities::people_name::PersonName;
+use crate::domain::users::entities::user_entity::UserEntity;
+use crate::infra::mailer::{Email, Mailer};
+use crate::shared::security::password::{PasswordHasher, PasswordMatch};
+use crate::shared::security::password_policy::{self, PasswordContext};
+use crate::shared::security::token::{generate_token, hash_token};
+use crate::shared::types::result::DomainResult;
+
+/// =========================
+/// AUTHENTICATE
+/// =========================
+/// Unknown emails and wrong passwords both yield `NotFound`
+/// so callers cannot tell which one failed, nor time it: an unknown
+/// email is still checked against a dummy hash. Hashes made with outdated
+/// argon2 parameters are upgraded in place.
+pub async fn authenticate(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    req: &LoginRequest,
+) -> DomainResult<UserEntity, AuthError> {
+    let user = match sqlx::query_as::<_, UserEntity>(
+        r#"
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
+        FROM users
+        WHERE email = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(&req.email)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            hasher.verify_dummy(&req.password);
+            return DomainResult::NotFound;
+        }
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match hasher.verify(&req.password, &user.password) {
+        Ok(PasswordMatch::Yes) => DomainResult::Ok(user),
+        Ok(PasswordMatch::NeedsRehash) => {
+            rehash_password(pool, hasher, &user, &req.password).await;
+            DomainResult::Ok(user)
+        }
+        Ok(PasswordMatch::No) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(AuthError::Hashing(e)),
+    }
+}
+
+/// =========================
+/// IS ACTIVE
+/// =========================
+/// Whether an access token's subject still exists and isn't soft-deleted;
+/// deleting a user doesn't recall the access tokens already issued
+pub async fn is_active(pool: &PgPool, user_id: Uuid) -> DomainResult<bool, AuthError> {
+    let active = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS (SELECT 1 FROM users WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await;
+
+    match active {
+        Ok(a) => DomainResult::Ok(a),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// Best effort: a failed upgrade must not fail the login
+async fn rehash_password(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    user: &UserEntity,
+    password: &str,
+) {
+    let hash = match hasher.hash(password) {
+        Ok(h) => h,
+        Err(e) => {
+            warn!(user_id = %user.id, error = %e, "password rehash failed");
+            return;
+        }
+    };
+
+    // skip if the password changed since we read it
+    let res = sqlx::query("UPDATE users SET password = $1 WHERE id = $2 AND password = $3")
+        .bind(hash)
+        .bind(user.id)
+        .bind(&user.password)
+        .execute(pool)
+        .await;
+
+    if let Err(e) = res {
+        warn!(user_id = %user.id, error = %e, "storing rehashed password failed");
+    }
+}
+
+/// =========================
+/// ISSUE REFRESH TOKEN (new family)
+/// =========================
+pub async fn issue_refresh_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    ttl_secs: i64,
+) -> DomainResult<String, AuthError> {
+    let mut conn = match pool.acquire().await {
+        Ok(c) => c,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match insert_refresh_token(&mut conn, user_id, Uuid::new_v4(), ttl_secs).await {
+        Ok(token) => DomainResult::Ok(token),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// ROTATE REFRESH TOKEN
+/// =========================
+/// Exchanges a live refresh token for a new one in the same family.
+/// Presenting a token that was already rotated (or revoked) is treated
+/// as theft: the whole family is revoked and `NotFound` is returned.
+pub async fn rotate_refresh_token(
+    pool: &PgPool,
+    token: &str,
+    ttl_secs: i64,
+) -> DomainResult<(UserEntity, String), AuthError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ lock the presented token
+    let current = match sqlx::query_as::<_, RefreshTokenEntity>(
+        r#"
+        SELECT id, user_id, family_id, expires_at, rotated_at, revoked_at
+        FROM refresh_tokens
+        WHERE token_hash = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(t)) => t,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 2️⃣ reuse detection → revoke the whole family
+    if current.rotated_at.is_some() || current.revoked_at.is_some() {
+        warn!(
+            user_id = %current.user_id,
+            family_id = %current.family_id,
+            "refresh token reuse detected, revoking family"
+        );
+
+        if let Err(e) = revoke_family(&mut tx, current.family_id).await {
+            return DomainResult::Err(e.into());
+        }
+        if let Err(e) = tx.commit().await {
+            return DomainResult::Err(e.into());
+        }
+        return DomainResult::NotFound;
+    }
+
+    // 3️⃣ mark as rotated, rejecting expired tokens
+    let live = match sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET rotated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND expires_at > CURRENT_TIMESTAMP
+        "#,
+    )
+    .bind(current.id)
+    .execute(&mut *tx)
+    .await
+    {
+        Ok(r) => r.rows_affected() > 0,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if !live {
+        return DomainResult::NotFound;
+    }
+
+    let user = match sqlx::query_as::<_, UserEntity>(
+        r#"
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(current.user_id)
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 4️⃣ issue the successor in the same family
+    let next = match insert_refresh_token(&mut tx, user.id, current.family_id, ttl_secs).await {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    DomainResult::Ok((user, next))
+}
+
+/// =========================
+/// REQUEST PASSWORD RESET
+/// =========================
+/// Emails a single-use reset link, superseding any outstanding one.
+/// Unknown emails yield `NotFound`, which must not reach the client.
+pub async fn request_password_reset(
+    pool: &PgPool,
+    mailer: &dyn Mailer,
+    email: &str,
+    ttl_secs: i64,
+    reset_url: &str,
+) -> DomainResult<(), AuthError> {
+    let user = match sqlx::query_as::<_, UserEntity>(
+        r#"
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
+        FROM users
+        WHERE email = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(email)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let token = generate_token();
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if let Err(e) = consume_reset_tokens(&mut tx, user.id).await {
+        return DomainResult::Err(e.into());
+    }
+
+    let res = sqlx::query(
+        r#"
+        INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, CURRENT_TIMESTAMP + make_interval(secs => $3))
+        "#,
+    )
+    .bind(user.id)
+    .bind(hash_token(&token))
+    .bind(ttl_secs as f64)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    let message = Email {
+        to: user.email,
+        subject: "Reset your password".to_string(),
+        body: format!(
+            "Someone asked to reset the password for this account.\n\n\
+             Open the link below to choose a new one. It expires in {} minutes \
+             and can only be used once.\n\n{}?token={}\n\n\
+             If this wasn't you, you can ignore this email.",
+            ttl_secs / 60,
+            reset_url,
+            token
+        ),
+    };
+
+    match mailer.send(&message).await {
+        Ok(()) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(AuthError::Mail(e.to_string())),
+    }
+}
+
+/// =========================
+/// RESET PASSWORD
+/// =========================
+/// Redeems a reset token. Unknown, used or expired tokens, and those of
+/// deleted users, yield `NotFound`. On success every session of the user
+/// is revoked.
+pub async fn reset_password(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    policy: &PasswordPolicy,
+    token: &str,
+    new_password: &str,
+) -> DomainResult<(), AuthError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ lock the live token
+    let reset = match sqlx::query_as::<_, PasswordResetTokenEntity>(
+        r#"
+        SELECT id, user_id, expires_at, used_at
+        FROM password_reset_tokens
+        WHERE token_hash = $1
+            AND used_at IS NULL
+            AND expires_at > CURRENT_TIMESTAMP
+        FOR UPDATE
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(t)) => t,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 2️⃣ apply the password policy against the user's own details; a token
+    // issued before the account was deleted is as good as unknown
+    let email = match sqlx::query_scalar::<_, String>(
+        "SELECT email FROM users WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(reset.user_id)
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(e)) => e,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let names: Vec<PersonName> = match users::usecases::find_names(&mut *tx, reset.user_id).await {
+        Ok(n) => n.into_iter().map(PersonName::from).collect(),
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let context = PasswordContext {
+        email: Some(&email),
+        names: names.iter().flat_map(PersonName::parts).collect(),
+    };
+    let violations = password_policy::check(policy, "new_password", new_password, &context);
+    if !violations.is_empty() {
+        return DomainResult::Err(AuthError::WeakPassword(violations));
+    }
+
+    let hashed_password = match hasher.hash(new_password) {
+        Ok(h) => h,
+        Err(e) => return DomainResult::Err(AuthError::Hashing(e)),
+    };
+
+    // 3️⃣ store it, burn every outstanding token and sign out everywhere
+    let res = sqlx::query(
+        "UPDATE users SET password = $1, updated_at = CURRENT_TIMESTAMP, version = version + 1 WHERE id = $2",
+    )
+    .bind(hashed_password)
+    .bind(reset.user_id)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = consume_reset_tokens(&mut tx, reset.user_id).await {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = revoke_user_refresh_tokens(&mut tx, reset.user_id).await {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = revoke_user_sessions(&mut tx, reset.user_id).await {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    DomainResult::Ok(())
+}
+
+/// =========================
+/// SEND EMAIL VERIFICATION
+/// =========================
+/// Emails a link proving ownership of `email`, superseding earlier links.
+pub async fn send_email_verification(
+    pool: &PgPool,
+    mailer: &dyn Mailer,
+    user_id: Uuid,
+    email: &str,
+    ttl_secs: i64,
+    verify_url: &str,
+) -> DomainResult<(), AuthError> {
+    let token = generate_token();
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if let Err(e) = consume_verification_tokens(&mut tx, user_id).await {
+        return DomainResult::Err(e.into());
+    }
+
+    let res = sqlx::query(
+        r#"
+        INSERT INTO email_verification_tokens (user_id, email, token_hash, expires_at)
+        VALUES ($1, $2, $3, CURRENT_TIMESTAMP + make_interval(secs => $4))
+        "#,
+    )
+    .bind(user_id)
+    .bind(email)
+    .bind(hash_token(&token))
+    .bind(ttl_secs as f64)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    let message = Email {
+        to: email.to_string(),
+        subject: "Verify your email address".to_string(),
+        body: format!(
+            "Please confirm this is your email address by opening the link below. \
+             It expires in {} hours.\n\n{}?token={}\n\n\
+             If you didn't create an account, you can ignore this email.",
+            ttl_secs / 3600,
+            verify_url,
+            token
+        ),
+    };
+
+    match mailer.send(&message).await {
+        Ok(()) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(AuthError::Mail(e.to_string())),
+    }
+}
+
+/// =========================
+/// RESEND EMAIL VERIFICATION
+/// =========================
+/// Unknown or already verified addresses yield `NotFound`,
+/// which must not reach the client.
+pub async fn resend_email_verification(
+    pool: &PgPool,
+    mailer: &dyn Mailer,
+    email: &str,
+    ttl_secs: i64,
+    verify_url: &str,
+) -> DomainResult<(), AuthError> {
+    let user_id = match sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM users WHERE email = $1 AND verified_at IS NULL AND deleted_at IS NULL",
+    )
+    .bind(email)
+    .fetch_optional(pool)
+    .await
+    {
+        Ok(Some(id)) => id,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    send_email_verification(pool, mailer, user_id, email, ttl_secs, verify_url).await
+}
+
+/// =========================
+/// VERIFY EMAIL
+/// =========================
+/// Redeems a verification token. Unknown, used or expired tokens, and
+/// tokens for an address the user has since changed, yield `NotFound`.
+pub async fn verify_email(pool: &PgPool, token: &str) -> DomainResult<(), AuthError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ lock the live token
+    let verification = match sqlx::query_as::<_, EmailVerificationTokenEntity>(
+        r#"
+        SELECT id, user_id, email, expires_at, used_at
+        FROM email_verification_tokens
+        WHERE token_hash = $1
+            AND used_at IS NULL
+            AND expires_at > CURRENT_TIMESTAMP
+        FOR UPDATE
+        "#,
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(t)) => t,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 2️⃣ only counts if the address is still the user's current one
+    let verified = match sqlx::query(
+        r#"
+        UPDATE users
+        SET verified_at = CURRENT_TIMESTAMP, version = version + 1
+        WHERE id = $1 AND email = $2
+        "#,
+    )
+    .bind(verification.user_id)
+    .bind(&verification.email)
+    .execute(&mut *tx)
+    .await
+    {
+        Ok(r) => r.rows_affected() > 0,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 3️⃣ burn the token (and its siblings once the address is verified)
+    let res = if verified {
+        consume_verification_tokens(&mut tx, verification.user_id).await
+    } else {
+        sqlx::query(
+            "UPDATE email_verification_tokens SET used_at = CURRENT_TIMESTAMP WHERE id = $1",
+        )
+        .bind(verification.id)
+        .execute(&mut *tx)
+        .await
+        .map(|_| ())
+    };
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    if verified {
+        DomainResult::Ok(())
+    } else {
+        DomainResult::NotFound
+    }
+}
+
+/// =========================
+/// HELPERS
+/// =========================
+async fn insert_refresh_token(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    family_id: Uuid,
+    ttl_secs: i64,
+) -> Result<String, sqlx::Error> {
+    let token = generate_token();
+
+    sqlx::query(
+        r#"
+        INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+        VALUES ($1, $2, $3, CURRENT_TIMESTAMP + make_interval(secs => $4))
+        "#,
+    )
+    .bind(user_id)
+    .bind(family_id)
+    .bind(hash_token(&token))
+    .bind(ttl_secs as f64)
+    .execute(conn)
+    .await?;
+
+    Ok(token)
+}
+
+async fn revoke_family(conn: &mut PgConnection, family_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE family_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(family_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn revoke_user_refresh_tokens(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn revoke_user_sessions(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn consume_reset_tokens(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE password_reset_tokens
+        SET used_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND used_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn consume_verification_tokens(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE email_verification_tokens
+        SET used_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND used_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
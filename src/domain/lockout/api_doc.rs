@@ -0,0 +1,5 @@
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(super::handlers::unlock_user))]
+pub struct LockoutApi;
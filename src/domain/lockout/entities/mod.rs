@@ -0,0 +1,3 @@
+pub mod throttle_scope;
+
+pub use throttle_scope::ThrottleScope;
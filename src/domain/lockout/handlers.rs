@@ -0,0 +1,33 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::usecases;
+use crate::app::state::AppState;
+use crate::shared::error::AppError;
+use crate::shared::extractors::ValidatedPath;
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    delete,
+    path = "",
+    responses(
+        (status = 200, description = "Lift a login lockout and reset its backoff"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Missing users:unlock permission"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::unlock_user(&state.db, id).await {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{error, info};
+
+use super::usecases;
+use crate::app::config::config::LoginThrottle;
+use crate::shared::types::result::DomainResult;
+
+/// Periodically deletes throttle rows that no longer lock or count
+/// anything. Does nothing when `purge_interval_secs` is `0`.
+pub fn spawn_purge_expired(pool: PgPool, policy: LoginThrottle) {
+    if policy.purge_interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(policy.purge_interval_secs));
+
+        loop {
+            ticker.tick().await;
+
+            match usecases::purge_expired(&pool, &policy).await {
+                DomainResult::Ok(0) => {}
+                DomainResult::Ok(purged) => info!(purged, "purged stale login throttles"),
+                DomainResult::Err(e) => error!(error = %e, "purging login throttles failed"),
+                _ => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,6 @@
+pub mod api_doc;
+pub mod entities;
+pub mod handlers;
+pub mod jobs;
+pub mod routes;
+pub mod usecases;
@@ -0,0 +1,13 @@
+use axum::Router;
+use axum::routing::delete;
+
+use super::handlers;
+use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::RequirePermission;
+
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", delete(handlers::unlock_user))
+        .route_layer(RequirePermission::new(state, Permission::UsersUnlock))
+}
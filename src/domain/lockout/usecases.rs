@@ -0,0 +1,200 @@
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::entities::ThrottleScope;
+use crate::app::config::config::LoginThrottle;
+use crate::shared::db_error::DbError;
+use crate::shared::types::result::DomainResult;
+
+/// Accounts are tracked by email so unknown addresses are throttled too
+pub fn account_key(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// =========================
+/// RETRY AFTER
+/// =========================
+/// Seconds until the longest active lock on any of `keys` ends, `None`
+/// when none is locked.
+pub async fn retry_after(
+    pool: &PgPool,
+    keys: &[(ThrottleScope, &str)],
+) -> DomainResult<Option<i64>, DbError> {
+    let (scopes, keys): (Vec<&str>, Vec<&str>) = keys
+        .iter()
+        .map(|(scope, key)| (scope.as_str(), *key))
+        .unzip();
+
+    let remaining = sqlx::query_scalar::<_, Option<i64>>(
+        r#"
+        SELECT CEIL(EXTRACT(EPOCH FROM MAX(t.locked_until) - CURRENT_TIMESTAMP))::BIGINT
+        FROM login_throttles t
+        JOIN UNNEST($1::VARCHAR[], $2::VARCHAR[]) AS k(scope, key)
+          ON k.scope = t.scope AND k.key = t.key
+        WHERE t.locked_until > CURRENT_TIMESTAMP
+        "#,
+    )
+    .bind(scopes)
+    .bind(keys)
+    .fetch_one(pool)
+    .await;
+
+    match remaining {
+        Ok(r) => DomainResult::Ok(r),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// RECORD FAILURE
+/// =========================
+/// Counts a failed login against `key`. Reaching `max_failures` locks it
+/// for `lockout_base_secs`, doubling with every further lockout up to
+/// `lockout_max_secs`. Returns the lock duration when this failure
+/// triggered one.
+pub async fn record_failure(
+    pool: &PgPool,
+    policy: &LoginThrottle,
+    scope: ThrottleScope,
+    key: &str,
+    max_failures: i32,
+) -> DomainResult<Option<i64>, DbError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ bump the counter, starting over once failures have gone stale
+    let counts = sqlx::query_as::<_, (i32, i32)>(
+        r#"
+        INSERT INTO login_throttles AS t (scope, key, failed_count)
+        VALUES ($1, $2, 1)
+        ON CONFLICT (scope, key) DO UPDATE
+        SET failed_count = CASE
+                WHEN t.last_failed_at < CURRENT_TIMESTAMP - make_interval(secs => $3) THEN 1
+                ELSE t.failed_count + 1
+            END,
+            lockout_count = CASE
+                WHEN t.last_failed_at < CURRENT_TIMESTAMP - make_interval(secs => $4) THEN 0
+                ELSE t.lockout_count
+            END,
+            last_failed_at = CURRENT_TIMESTAMP
+        RETURNING failed_count, lockout_count
+        "#,
+    )
+    .bind(scope.as_str())
+    .bind(key)
+    .bind(policy.failure_window_secs as f64)
+    .bind(policy.lockout_reset_secs as f64)
+    .fetch_one(&mut *tx)
+    .await;
+
+    let (failed_count, lockout_count) = match counts {
+        Ok(c) => c,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if failed_count < max_failures {
+        return match tx.commit().await {
+            Ok(()) => DomainResult::Ok(None),
+            Err(e) => DomainResult::Err(e.into()),
+        };
+    }
+
+    // 2️⃣ threshold reached → lock with exponential backoff
+    let lock_secs = lockout_secs(policy, lockout_count);
+
+    let res = sqlx::query(
+        r#"
+        UPDATE login_throttles
+        SET failed_count = 0,
+            lockout_count = lockout_count + 1,
+            locked_until = CURRENT_TIMESTAMP + make_interval(secs => $3)
+        WHERE scope = $1 AND key = $2
+        "#,
+    )
+    .bind(scope.as_str())
+    .bind(key)
+    .bind(lock_secs as f64)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    warn!(scope = scope.as_str(), key = %key, lock_secs, "login throttled");
+
+    DomainResult::Ok(Some(lock_secs))
+}
+
+/// =========================
+/// CLEAR
+/// =========================
+pub async fn clear(pool: &PgPool, scope: ThrottleScope, key: &str) -> DomainResult<(), DbError> {
+    let res = sqlx::query("DELETE FROM login_throttles WHERE scope = $1 AND key = $2")
+        .bind(scope.as_str())
+        .bind(key)
+        .execute(pool)
+        .await;
+
+    match res {
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// PURGE EXPIRED
+/// =========================
+/// Deletes rows that no longer lock anything and whose failures and
+/// backoff have both gone stale, i.e. that would start over anyway.
+pub async fn purge_expired(pool: &PgPool, policy: &LoginThrottle) -> DomainResult<u64, DbError> {
+    let res = sqlx::query(
+        r#"
+        DELETE FROM login_throttles
+        WHERE (locked_until IS NULL OR locked_until <= CURRENT_TIMESTAMP)
+          AND last_failed_at < CURRENT_TIMESTAMP - make_interval(secs => $1)
+        "#,
+    )
+    .bind(policy.failure_window_secs.max(policy.lockout_reset_secs) as f64)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) => DomainResult::Ok(r.rows_affected()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// UNLOCK USER (admin)
+/// =========================
+/// Lifts the account lock and resets its backoff. IP blocks are left alone.
+pub async fn unlock_user(pool: &PgPool, user_id: Uuid) -> DomainResult<(), DbError> {
+    let email = match sqlx::query_scalar::<_, String>("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+    {
+        Ok(Some(e)) => e,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    clear(pool, ThrottleScope::Account, &account_key(&email)).await
+}
+
+/// `base * 2^previous_lockouts`, capped at `lockout_max_secs`
+fn lockout_secs(policy: &LoginThrottle, previous_lockouts: i32) -> i64 {
+    let factor = 1i64 << previous_lockouts.clamp(0, 32);
+    policy
+        .lockout_base_secs
+        .saturating_mul(factor)
+        .min(policy.lockout_max_secs)
+}
@@ -0,0 +1,25 @@
+use utoipa::OpenApi;
+
+use super::dtos::create::AddMemberRequest;
+use super::dtos::response::MembershipResponse;
+use super::dtos::update::UpdateMemberRequest;
+use super::entities::SchoolRole;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::list_school_members,
+        super::handlers::add_member,
+        super::handlers::update_member,
+        super::handlers::remove_member
+    ),
+    components(schemas(MembershipResponse, AddMemberRequest, UpdateMemberRequest, SchoolRole))
+)]
+pub struct SchoolMembersApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(super::handlers::list_user_schools),
+    components(schemas(MembershipResponse))
+)]
+pub struct UserSchoolsApi;
@@ -0,0 +1,40 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::memberships::entities::SchoolRole;
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AddMemberRequest {
+    pub user_id: Uuid,
+    pub role: SchoolRole,
+    /// Defaults to today
+    pub enrolled_at: Option<NaiveDate>,
+    pub left_at: Option<NaiveDate>,
+}
+
+impl Validate for AddMemberRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_dates(v, self.enrolled_at, self.left_at);
+    }
+}
+
+/// Only checkable when both dates are in the body; otherwise the
+/// `school_members` CHECK constraint catches it.
+pub fn validate_dates(
+    v: &mut Validator,
+    enrolled_at: Option<NaiveDate>,
+    left_at: Option<NaiveDate>,
+) {
+    if let (Some(enrolled_at), Some(left_at)) = (enrolled_at, left_at)
+        && left_at < enrolled_at
+    {
+        v.add(
+            "left_at",
+            "before_enrolled_at",
+            "must not be before enrolled_at",
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::memberships::entities::SchoolRole;
+use crate::domain::memberships::entities::membership_entity::MembershipEntity;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MembershipResponse {
+    pub school_id: Uuid,
+    pub user_id: Uuid,
+    pub role: SchoolRole,
+    pub enrolled_at: NaiveDate,
+    pub left_at: Option<NaiveDate>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<MembershipEntity> for MembershipResponse {
+    type Error = String;
+
+    fn try_from(entity: MembershipEntity) -> Result<Self, Self::Error> {
+        Ok(Self {
+            school_id: entity.school_id,
+            user_id: entity.user_id,
+            role: entity.role.parse()?,
+            enrolled_at: entity.enrolled_at,
+            left_at: entity.left_at,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        })
+    }
+}
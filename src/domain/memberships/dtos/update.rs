@@ -0,0 +1,40 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::create::validate_dates;
+use crate::domain::memberships::entities::SchoolRole;
+use crate::shared::types::patch::Patch;
+use crate::shared::validation::{Validate, Validator};
+
+/// Merge patch of a membership: members left out are kept, and
+/// `"left_at": null` makes a former member active again. `role` and
+/// `enrolled_at` can't be removed.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateMemberRequest {
+    #[serde(default)]
+    #[schema(value_type = Option<SchoolRole>)]
+    pub role: Patch<SchoolRole>,
+    #[serde(default)]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub enrolled_at: Patch<NaiveDate>,
+    #[serde(default)]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub left_at: Patch<NaiveDate>,
+}
+
+impl Validate for UpdateMemberRequest {
+    fn validate(&self, v: &mut Validator) {
+        if matches!(self.role, Patch::Null) {
+            v.add("role", "required", "must not be null");
+        }
+        if matches!(self.enrolled_at, Patch::Null) {
+            v.add("enrolled_at", "required", "must not be null");
+        }
+        validate_dates(
+            v,
+            self.enrolled_at.as_value().copied(),
+            self.left_at.as_value().copied(),
+        );
+    }
+}
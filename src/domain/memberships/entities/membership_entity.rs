@@ -0,0 +1,14 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub struct MembershipEntity {
+    pub school_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String, // "student", "teacher", "admin"
+    pub enrolled_at: NaiveDate,
+    pub left_at: Option<NaiveDate>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
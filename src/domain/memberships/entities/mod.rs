@@ -0,0 +1,4 @@
+pub mod membership_entity;
+pub mod school_role;
+
+pub use school_role::SchoolRole;
@@ -0,0 +1,36 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Role a user holds within a single school
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SchoolRole {
+    Student,
+    Teacher,
+    Admin,
+}
+
+impl SchoolRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchoolRole::Student => "student",
+            SchoolRole::Teacher => "teacher",
+            SchoolRole::Admin => "admin",
+        }
+    }
+}
+
+impl FromStr for SchoolRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "student" => Ok(SchoolRole::Student),
+            "teacher" => Ok(SchoolRole::Teacher),
+            "admin" => Ok(SchoolRole::Admin),
+            other => Err(format!("unknown school role `{}`", other)),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::fmt;
+
+use crate::shared::db_error::DbError;
+use crate::shared::error::AppError;
+
+/// Failures of the membership usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum MembershipError {
+    /// `left_at` is before `enrolled_at`
+    InvalidDates,
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for MembershipError {
+    fn from(err: sqlx::Error) -> Self {
+        DbError::from(err).into()
+    }
+}
+
+impl From<DbError> for MembershipError {
+    fn from(err: DbError) -> Self {
+        match err {
+            db if db.is_check_violation("school_members_check") => MembershipError::InvalidDates,
+            db => MembershipError::Db(db),
+        }
+    }
+}
+
+impl fmt::Display for MembershipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MembershipError::InvalidDates => write!(f, "left_at must not be before enrolled_at"),
+            MembershipError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<MembershipError> for AppError {
+    fn from(err: MembershipError) -> Self {
+        match err {
+            MembershipError::InvalidDates => AppError::bad_request(err.to_string()),
+            MembershipError::Db(db) => db.into(),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::dtos::create::AddMemberRequest;
+use super::dtos::response::MembershipResponse;
+use super::dtos::update::UpdateMemberRequest;
+use super::entities::membership_entity::MembershipEntity;
+use super::usecases::{self, SchoolAccess};
+use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::require_permission;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ValidatedJson, ValidatedPath};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses(
+        (status = 200, description = "List members of a school", body = [MembershipResponse]),
+        (status = 403, description = "Caller is not a member of this school"),
+        (status = 404, description = "School not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    )
+)]
+pub async fn list_school_members(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<Vec<MembershipResponse>>, AppError> {
+    require_access(&state, &user, id, SchoolAccess::Member).await?;
+
+    match usecases::list_school_members(&state.db, id).await {
+        DomainResult::Ok(members) => Ok(ApiResponse::ok(to_responses(members)?)),
+        DomainResult::NotFound => Err(AppError::not_found("School not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = AddMemberRequest,
+    responses(
+        (status = 201, description = "Add or re-enroll a member", body = MembershipResponse),
+        (status = 400, description = "left_at is before enrolled_at"),
+        (status = 403, description = "Caller cannot manage this school"),
+        (status = 404, description = "School or user not found"),
+        (status = 422, description = "Request body failed validation")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    )
+)]
+pub async fn add_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(req): ValidatedJson<AddMemberRequest>,
+) -> Result<ApiResponse<MembershipResponse>, AppError> {
+    require_access(&state, &user, id, SchoolAccess::Manage).await?;
+
+    match usecases::add_member(&state.db, id, req).await {
+        DomainResult::Ok(member) => Ok(ApiResponse::created(
+            MembershipResponse::try_from(member).map_err(AppError::internal_server_error)?,
+        )),
+        DomainResult::NotFound => Err(AppError::not_found("School or user not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/{user_id}",
+    request_body = UpdateMemberRequest,
+    responses(
+        (status = 200, description = "Update a membership", body = MembershipResponse),
+        (status = 403, description = "Caller cannot manage this school"),
+        (status = 404, description = "Membership not found"),
+        (status = 422, description = "Request body failed validation")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn update_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath((id, user_id)): ValidatedPath<(Uuid, Uuid)>,
+    ValidatedJson(req): ValidatedJson<UpdateMemberRequest>,
+) -> Result<ApiResponse<MembershipResponse>, AppError> {
+    require_access(&state, &user, id, SchoolAccess::Manage).await?;
+
+    match usecases::update_member(&state.db, id, user_id, req).await {
+        DomainResult::Ok(member) => Ok(ApiResponse::ok(
+            MembershipResponse::try_from(member).map_err(AppError::internal_server_error)?,
+        )),
+        DomainResult::NotFound => Err(AppError::not_found("Membership not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{user_id}",
+    responses(
+        (status = 200, description = "Remove a member"),
+        (status = 403, description = "Caller cannot manage this school"),
+        (status = 404, description = "Membership not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("user_id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn remove_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath((id, user_id)): ValidatedPath<(Uuid, Uuid)>,
+) -> Result<ApiResponse<()>, AppError> {
+    require_access(&state, &user, id, SchoolAccess::Manage).await?;
+
+    match usecases::remove_member(&state.db, id, user_id).await {
+        DomainResult::Ok(_) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("Membership not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses(
+        (status = 200, description = "List schools a user belongs to", body = [MembershipResponse]),
+        (status = 403, description = "Caller is neither the user nor has users:read"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn list_user_schools(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<Vec<MembershipResponse>>, AppError> {
+    if user.id != id {
+        require_permission(&state, &user, Permission::UsersRead).await?;
+    }
+
+    match usecases::list_user_schools(&state.db, id).await {
+        DomainResult::Ok(schools) => Ok(ApiResponse::ok(to_responses(schools)?)),
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+/// Reject callers below the `needed` access level for `school_id`
+async fn require_access(
+    state: &AppState,
+    user: &AuthUser,
+    school_id: Uuid,
+    needed: SchoolAccess,
+) -> Result<(), AppError> {
+    // school roles can't be narrowed per key, so require the matching scope
+    let scope = match needed {
+        SchoolAccess::Manage => Permission::SchoolsWrite,
+        _ => Permission::SchoolsRead,
+    };
+    if !user.allows(scope) {
+        return Err(AppError::Forbidden);
+    }
+
+    let access = match usecases::school_access(
+        &state.db,
+        user,
+        school_id,
+        &state.config.auth.two_factor_required_roles,
+    )
+    .await
+    {
+        DomainResult::Ok(access) => access,
+        DomainResult::NotFound => SchoolAccess::None,
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    match (needed, access) {
+        (_, SchoolAccess::Manage) => Ok(()),
+        (SchoolAccess::Member, SchoolAccess::Member) => Ok(()),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
+fn to_responses(members: Vec<MembershipEntity>) -> Result<Vec<MembershipResponse>, AppError> {
+    members
+        .into_iter()
+        .map(MembershipResponse::try_from)
+        .collect::<Result<_, _>>()
+        .map_err(AppError::internal_server_error)
+}
@@ -0,0 +1,23 @@
+use axum::Router;
+use axum::routing::{get, put};
+
+use super::handlers;
+use crate::app::state::AppState;
+
+/// Mounted at `/schools/{id}/members`
+pub fn school_members_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::list_school_members).post(handlers::add_member),
+        )
+        .route(
+            "/{user_id}",
+            put(handlers::update_member).delete(handlers::remove_member),
+        )
+}
+
+/// Mounted at `/users/{id}/schools`
+pub fn user_schools_router() -> Router<AppState> {
+    Router::new().route("/", get(handlers::list_user_schools))
+}
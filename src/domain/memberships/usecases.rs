@@ -0,0 +1,244 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::dtos::create::AddMemberRequest;
+use super::dtos::update::UpdateMemberRequest;
+use super::entities::SchoolRole;
+use super::entities::membership_entity::MembershipEntity;
+use super::error::MembershipError;
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::usecases::{Authorization, authorize};
+use crate::shared::types::result::DomainResult;
+
+/// What a caller may do with a school's memberships
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchoolAccess {
+    None,
+    /// Active member of the school: may list its members
+    Member,
+    /// Global `schools:write` or school admin: may manage members
+    Manage,
+}
+
+/// =========================
+/// SCHOOL ACCESS
+/// =========================
+/// The global grant counts only when it would pass the route guard, so a
+/// caller still owing two-factor enrollment falls back to their membership.
+pub async fn school_access(
+    pool: &PgPool,
+    user: &AuthUser,
+    school_id: Uuid,
+    two_factor_required_roles: &[String],
+) -> DomainResult<SchoolAccess, MembershipError> {
+    match authorize(
+        pool,
+        user,
+        Permission::SchoolsWrite,
+        two_factor_required_roles,
+    )
+    .await
+    {
+        DomainResult::Ok(Authorization::Granted) => return DomainResult::Ok(SchoolAccess::Manage),
+        DomainResult::Ok(_) | DomainResult::NotFound => {}
+        DomainResult::Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    let role = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT role
+        FROM school_members
+        WHERE school_id = $1
+          AND user_id = $2
+          AND (left_at IS NULL OR left_at > CURRENT_DATE)
+        "#,
+    )
+    .bind(school_id)
+    .bind(user.id)
+    .fetch_optional(pool)
+    .await;
+
+    match role {
+        Ok(Some(r)) if r == SchoolRole::Admin.as_str() => DomainResult::Ok(SchoolAccess::Manage),
+        Ok(Some(_)) => DomainResult::Ok(SchoolAccess::Member),
+        Ok(None) => DomainResult::Ok(SchoolAccess::None),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST SCHOOL MEMBERS
+/// =========================
+pub async fn list_school_members(
+    pool: &PgPool,
+    school_id: Uuid,
+) -> DomainResult<Vec<MembershipEntity>, MembershipError> {
+    match exists(pool, "schools", school_id).await {
+        Ok(true) => {}
+        Ok(false) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    let members = sqlx::query_as::<_, MembershipEntity>(
+        r#"
+        SELECT school_id, user_id, role, enrolled_at, left_at, created_at, updated_at
+        FROM school_members
+        WHERE school_id = $1
+        ORDER BY enrolled_at DESC, user_id
+        "#,
+    )
+    .bind(school_id)
+    .fetch_all(pool)
+    .await;
+
+    match members {
+        Ok(m) => DomainResult::Ok(m),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST USER SCHOOLS
+/// =========================
+pub async fn list_user_schools(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> DomainResult<Vec<MembershipEntity>, MembershipError> {
+    match exists(pool, "users", user_id).await {
+        Ok(true) => {}
+        Ok(false) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    let schools = sqlx::query_as::<_, MembershipEntity>(
+        r#"
+        SELECT school_id, user_id, role, enrolled_at, left_at, created_at, updated_at
+        FROM school_members
+        WHERE user_id = $1
+        ORDER BY enrolled_at DESC, school_id
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await;
+
+    match schools {
+        Ok(s) => DomainResult::Ok(s),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// ADD MEMBER
+/// =========================
+/// Re-adding an existing member re-enrolls them with the new role and dates.
+pub async fn add_member(
+    pool: &PgPool,
+    school_id: Uuid,
+    req: AddMemberRequest,
+) -> DomainResult<MembershipEntity, MembershipError> {
+    for (table, id) in [("schools", school_id), ("users", req.user_id)] {
+        match exists(pool, table, id).await {
+            Ok(true) => {}
+            Ok(false) => return DomainResult::NotFound,
+            Err(e) => return DomainResult::Err(e.into()),
+        }
+    }
+
+    let member = sqlx::query_as::<_, MembershipEntity>(
+        r#"
+        INSERT INTO school_members (school_id, user_id, role, enrolled_at, left_at)
+        VALUES ($1, $2, $3, COALESCE($4, CURRENT_DATE), $5)
+        ON CONFLICT (school_id, user_id) DO UPDATE
+        SET
+            role = EXCLUDED.role,
+            enrolled_at = EXCLUDED.enrolled_at,
+            left_at = EXCLUDED.left_at,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING school_id, user_id, role, enrolled_at, left_at, created_at, updated_at
+        "#,
+    )
+    .bind(school_id)
+    .bind(req.user_id)
+    .bind(req.role.as_str())
+    .bind(req.enrolled_at)
+    .bind(req.left_at)
+    .fetch_one(pool)
+    .await;
+
+    match member {
+        Ok(m) => DomainResult::Ok(m),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// UPDATE MEMBER
+/// =========================
+/// Only the members present in `req` change; a `null` `left_at` clears it.
+pub async fn update_member(
+    pool: &PgPool,
+    school_id: Uuid,
+    user_id: Uuid,
+    req: UpdateMemberRequest,
+) -> DomainResult<MembershipEntity, MembershipError> {
+    let member = sqlx::query_as::<_, MembershipEntity>(
+        r#"
+        UPDATE school_members
+        SET
+            role = COALESCE($1, role),
+            enrolled_at = COALESCE($2, enrolled_at),
+            left_at = CASE WHEN $3 THEN left_at ELSE $4 END,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE school_id = $5 AND user_id = $6
+        RETURNING school_id, user_id, role, enrolled_at, left_at, created_at, updated_at
+        "#,
+    )
+    .bind(req.role.as_value().map(|r| r.as_str()))
+    .bind(req.enrolled_at.as_value())
+    .bind(req.left_at.is_absent())
+    .bind(req.left_at.as_value())
+    .bind(school_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await;
+
+    match member {
+        Ok(Some(m)) => DomainResult::Ok(m),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// REMOVE MEMBER
+/// =========================
+pub async fn remove_member(
+    pool: &PgPool,
+    school_id: Uuid,
+    user_id: Uuid,
+) -> DomainResult<(), MembershipError> {
+    let result = sqlx::query("DELETE FROM school_members WHERE school_id = $1 AND user_id = $2")
+        .bind(school_id)
+        .bind(user_id)
+        .execute(pool)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// `table` is always a literal from this module, never user input
+async fn exists(pool: &PgPool, table: &str, id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+        table
+    ))
+    .bind(id)
+    .fetch_one(pool)
+    .await
+}
@@ -1 +1,11 @@
+pub mod api_keys;
+pub mod auth;
+pub mod lockout;
+pub mod memberships;
+pub mod oidc;
+pub mod products;
+pub mod roles;
+pub mod schools;
+pub mod sessions;
+pub mod two_factor;
 pub mod users;
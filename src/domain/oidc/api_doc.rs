@@ -0,0 +1,15 @@
+use utoipa::OpenApi;
+
+use super::dtos::authorize::{AuthorizeResponse, ProvidersResponse};
+use super::dtos::callback::CallbackRequest;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::list_providers,
+        super::handlers::authorize,
+        super::handlers::callback
+    ),
+    components(schemas(ProvidersResponse, AuthorizeResponse, CallbackRequest))
+)]
+pub struct OidcApi;
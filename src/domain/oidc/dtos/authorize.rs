@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProvidersResponse {
+    pub providers: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthorizeResponse {
+    /// Send the browser here; the provider redirects back with `code` and `state`
+    pub authorization_url: String,
+    /// Seconds until `state` can no longer be redeemed
+    pub expires_in: i64,
+}
+
+/// Query of the mock provider's authorization endpoint
+#[derive(Debug, Deserialize)]
+pub struct MockAuthorizeQuery {
+    pub redirect_uri: String,
+    pub state: String,
+    pub nonce: String,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+    /// Email of the account to sign in as
+    pub login_hint: String,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+/// `code` and `state` as received on the redirect back from the provider
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CallbackRequest {
+    pub code: String,
+    pub state: String,
+    /// Start a cookie session instead of issuing tokens
+    #[serde(default)]
+    pub session: bool,
+}
+
+impl Validate for CallbackRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("code", &self.code)
+            .required("state", &self.state);
+    }
+}
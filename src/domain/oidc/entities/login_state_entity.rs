@@ -0,0 +1,8 @@
+use sqlx::FromRow;
+
+/// The secrets of a redeemed `state`
+#[derive(Debug, FromRow)]
+pub struct LoginStateEntity {
+    pub code_verifier: String,
+    pub nonce: String,
+}
@@ -0,0 +1,85 @@
+use std::fmt;
+
+use axum::http::StatusCode;
+
+use crate::infra::oidc::OidcError;
+use crate::shared::db_error::DbError;
+use crate::shared::error::AppError;
+
+/// Failures of the single sign-on usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum SsoError {
+    /// Unknown, expired or already used `state`
+    InvalidState,
+    /// Code exchange or ID token verification failed
+    Provider(OidcError),
+    /// The ID token doesn't carry the nonce sent with the request
+    NonceMismatch,
+    /// No email, or one the provider hasn't verified
+    EmailNotVerified,
+    /// No user with the identity's email
+    NoMatchingAccount,
+    /// More than one user has the identity's email, differing only in case
+    AmbiguousAccount,
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for SsoError {
+    fn from(err: sqlx::Error) -> Self {
+        SsoError::Db(err.into())
+    }
+}
+
+impl From<DbError> for SsoError {
+    fn from(err: DbError) -> Self {
+        SsoError::Db(err)
+    }
+}
+
+impl fmt::Display for SsoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SsoError::InvalidState => write!(f, "invalid or expired login state"),
+            SsoError::Provider(e) => write!(f, "{}", e),
+            SsoError::NonceMismatch => write!(f, "id token nonce mismatch"),
+            SsoError::EmailNotVerified => write!(f, "identity has no verified email"),
+            SsoError::NoMatchingAccount => write!(f, "no account matches the identity"),
+            SsoError::AmbiguousAccount => write!(f, "several accounts match the identity"),
+            SsoError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<SsoError> for AppError {
+    fn from(err: SsoError) -> Self {
+        match err {
+            SsoError::InvalidState => AppError::problem(
+                StatusCode::BAD_REQUEST,
+                "invalid_sso_state",
+                "Login request is unknown, expired or already used",
+            ),
+            SsoError::Provider(OidcError::Http(e)) => AppError::problem(
+                StatusCode::BAD_GATEWAY,
+                "sso_provider_unavailable",
+                format!("Identity provider unavailable: {}", e),
+            ),
+            SsoError::Provider(_) | SsoError::NonceMismatch => AppError::Unauthorized,
+            SsoError::EmailNotVerified => AppError::problem(
+                StatusCode::FORBIDDEN,
+                "sso_email_not_verified",
+                "The identity provider has not verified this email address",
+            ),
+            SsoError::NoMatchingAccount => AppError::problem(
+                StatusCode::FORBIDDEN,
+                "no_matching_account",
+                "No account matches this identity",
+            ),
+            SsoError::AmbiguousAccount => AppError::problem(
+                StatusCode::CONFLICT,
+                "ambiguous_account",
+                "Several accounts match this identity's email, sign in with a password instead",
+            ),
+            SsoError::Db(db) => db.into(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::header::USER_AGENT;
+use axum::response::Redirect;
+use axum_extra::extract::cookie::CookieJar;
+
+use super::dtos::authorize::{AuthorizeResponse, MockAuthorizeQuery, ProvidersResponse};
+use super::dtos::callback::CallbackRequest;
+use super::error::SsoError;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::auth::dtos::login::LoginResponse;
+use crate::domain::auth::handlers::{complete_login, two_factor_challenge};
+use crate::infra::oidc::{IdentityProvider, with_query};
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ClientIp, ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::shared::response::ApiResponse;
+use crate::shared::security::token::constant_time_eq;
+use crate::shared::types::result::DomainResult;
+
+fn provider(state: &AppState, name: &str) -> Result<Arc<dyn IdentityProvider>, AppError> {
+    state
+        .oidc
+        .get(name)
+        .ok_or_else(|| AppError::not_found("Identity provider not found"))
+}
+
+#[utoipa::path(
+    get,
+    path = "/providers",
+    responses(
+        (status = 200, description = "List configured identity providers", body = ProvidersResponse)
+    )
+)]
+pub async fn list_providers(
+    State(state): State<AppState>,
+) -> Result<ApiResponse<ProvidersResponse>, AppError> {
+    Ok(ApiResponse::ok(ProvidersResponse {
+        providers: state.oidc.names(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{provider}/authorize",
+    responses(
+        (status = 200, description = "Start a login, returns the provider's authorization URL", body = AuthorizeResponse),
+        (status = 404, description = "Identity provider not found")
+    ),
+    params(
+        ("provider" = String, Path, description = "Provider name")
+    )
+)]
+pub async fn authorize(
+    State(state): State<AppState>,
+    ValidatedPath(name): ValidatedPath<String>,
+) -> Result<ApiResponse<AuthorizeResponse>, AppError> {
+    let provider = provider(&state, &name)?;
+    let ttl_secs = state.config.oidc.state_ttl_secs;
+
+    match usecases::start_login(&state.db, provider.name(), ttl_secs).await {
+        DomainResult::Ok(pending) => Ok(ApiResponse::ok(AuthorizeResponse {
+            authorization_url: provider.authorization_url(
+                &pending.state,
+                &pending.nonce,
+                &pending.code_challenge,
+            ),
+            expires_in: ttl_secs,
+        })),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/{provider}/callback",
+    request_body = CallbackRequest,
+    responses(
+        (status = 200, description = "Issue tokens or start a cookie session, or a two-factor challenge when enabled", body = LoginResponse),
+        (status = 400, description = "Unknown, expired or used state, or cookie sessions not enabled"),
+        (status = 401, description = "Code rejected or ID token invalid"),
+        (status = 403, description = "Email not verified by the provider, or no matching account"),
+        (status = 404, description = "Identity provider not found"),
+        (status = 409, description = "Several accounts match the email, differing only in case"),
+        (status = 422, description = "Request body failed validation"),
+        (status = 502, description = "Identity provider unavailable")
+    ),
+    params(
+        ("provider" = String, Path, description = "Provider name")
+    )
+)]
+pub async fn callback(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    ValidatedPath(name): ValidatedPath<String>,
+    ValidatedJson(req): ValidatedJson<CallbackRequest>,
+) -> Result<(CookieJar, ApiResponse<LoginResponse>), AppError> {
+    let provider = provider(&state, &name)?;
+
+    if req.session && !state.config.session.enabled {
+        return Err(AppError::bad_request("Cookie sessions are not enabled"));
+    }
+
+    // 1️⃣ redeem the state, once
+    let login_state = match usecases::consume_state(&state.db, provider.name(), &req.state).await {
+        DomainResult::Ok(s) => s,
+        DomainResult::NotFound => return Err(SsoError::InvalidState.into()),
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    // 2️⃣ exchange the code with the PKCE verifier
+    let claims = provider
+        .exchange_code(&req.code, &login_state.code_verifier)
+        .await
+        .map_err(SsoError::Provider)?;
+
+    let nonce_matches = claims
+        .nonce
+        .as_deref()
+        .is_some_and(|n| constant_time_eq(n.as_bytes(), login_state.nonce.as_bytes()));
+    if !nonce_matches {
+        return Err(SsoError::NonceMismatch.into());
+    }
+
+    // 3️⃣ find or link the account
+    let user = match usecases::link_identity(&state.db, provider.name(), &claims).await {
+        DomainResult::Ok(user) => user,
+        DomainResult::NotFound => return Err(SsoError::NoMatchingAccount.into()),
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    // the provider doesn't replace this application's second factor
+    if let Some(challenge) = two_factor_challenge(&state, user.id).await? {
+        return Ok((CookieJar::new(), ApiResponse::ok(challenge)));
+    }
+
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    let (jar, response) =
+        complete_login(&state, &user, req.session, user_agent, ip.as_deref()).await?;
+
+    Ok((jar, ApiResponse::ok(response)))
+}
+
+/// Authorization endpoint of the in-process mock provider: signs in as
+/// `login_hint` without asking and redirects back with a code
+pub async fn mock_authorize(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<MockAuthorizeQuery>,
+) -> Result<Redirect, AppError> {
+    let mock = state
+        .oidc
+        .mock()
+        .ok_or_else(|| AppError::not_found("Route not found"))?;
+
+    // never redirect codes anywhere else
+    if query.redirect_uri != mock.redirect_uri() {
+        return Err(AppError::bad_request("redirect_uri is not registered"));
+    }
+    if query.code_challenge_method != "S256" {
+        return Err(AppError::bad_request("code_challenge_method must be S256"));
+    }
+
+    let code = mock.issue_code(&query.login_hint, &query.nonce, &query.code_challenge);
+
+    Ok(Redirect::to(&with_query(
+        &query.redirect_uri,
+        &[("code", &code), ("state", &query.state)],
+    )))
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{error, info};
+
+use super::usecases;
+use crate::app::config::config::Oidc;
+use crate::shared::types::result::DomainResult;
+
+/// Periodically deletes login states that can no longer be redeemed.
+/// Does nothing when `state_purge_interval_secs` is `0`.
+pub fn spawn_purge_states(pool: PgPool, oidc: Oidc) {
+    if oidc.state_purge_interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(oidc.state_purge_interval_secs));
+
+        loop {
+            ticker.tick().await;
+
+            match usecases::purge_states(&pool).await {
+                DomainResult::Ok(0) => {}
+                DomainResult::Ok(purged) => info!(purged, "purged spent sso login states"),
+                DomainResult::Err(e) => error!(error = %e, "purging sso login states failed"),
+                _ => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,8 @@
+pub mod api_doc;
+pub mod dtos;
+pub mod entities;
+pub mod error;
+pub mod handlers;
+pub mod jobs;
+pub mod routes;
+pub mod usecases;
@@ -0,0 +1,17 @@
+use axum::Router;
+use axum::routing::{get, post};
+
+use super::handlers;
+use crate::app::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/providers", get(handlers::list_providers))
+        .route("/{provider}/authorize", post(handlers::authorize))
+        .route("/{provider}/callback", post(handlers::callback))
+}
+
+/// The mock provider's own endpoints, mounted only when it is enabled
+pub fn mock_router() -> Router<AppState> {
+    Router::new().route("/authorize", get(handlers::mock_authorize))
+}
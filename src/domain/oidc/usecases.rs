@@ -0,0 +1,231 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::entities::login_state_entity::LoginStateEntity;
+use super::error::SsoError;
+use crate::domain::users::entities::user_entity::UserEntity;
+use crate::infra::oidc::IdentityClaims;
+use crate::shared::db_error::DbError;
+use crate::shared::security::token::{generate_token, hash_token, pkce_challenge};
+use crate::shared::types::result::DomainResult;
+
+/// Parameters of a new authorization request; only their server-side
+/// halves are stored
+pub struct PendingLogin {
+    pub state: String,
+    pub nonce: String,
+    pub code_challenge: String,
+}
+
+/// =========================
+/// START LOGIN
+/// =========================
+pub async fn start_login(
+    pool: &PgPool,
+    provider: &str,
+    ttl_secs: i64,
+) -> DomainResult<PendingLogin, DbError> {
+    let state = generate_token();
+    let nonce = generate_token();
+    let code_verifier = generate_token();
+
+    let res = sqlx::query(
+        r#"
+        INSERT INTO oidc_login_states (provider, state_hash, code_verifier, nonce, expires_at)
+        VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP + make_interval(secs => $5))
+        "#,
+    )
+    .bind(provider)
+    .bind(hash_token(&state))
+    .bind(&code_verifier)
+    .bind(&nonce)
+    .bind(ttl_secs as f64)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(_) => DomainResult::Ok(PendingLogin {
+            code_challenge: pkce_challenge(&code_verifier),
+            state,
+            nonce,
+        }),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// CONSUME STATE
+/// =========================
+/// Redeems `state` once; unknown, expired and reused states yield `NotFound`.
+pub async fn consume_state(
+    pool: &PgPool,
+    provider: &str,
+    state: &str,
+) -> DomainResult<LoginStateEntity, DbError> {
+    let login_state = sqlx::query_as::<_, LoginStateEntity>(
+        r#"
+        UPDATE oidc_login_states
+        SET consumed_at = CURRENT_TIMESTAMP
+        WHERE state_hash = $1
+          AND provider = $2
+          AND consumed_at IS NULL
+          AND expires_at > CURRENT_TIMESTAMP
+        RETURNING code_verifier, nonce
+        "#,
+    )
+    .bind(hash_token(state))
+    .bind(provider)
+    .fetch_optional(pool)
+    .await;
+
+    match login_state {
+        Ok(Some(s)) => DomainResult::Ok(s),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// PURGE STATES
+/// =========================
+/// Deletes states that can no longer be redeemed: used or expired.
+pub async fn purge_states(pool: &PgPool) -> DomainResult<u64, DbError> {
+    let res = sqlx::query(
+        r#"
+        DELETE FROM oidc_login_states
+        WHERE consumed_at IS NOT NULL OR expires_at <= CURRENT_TIMESTAMP
+        "#,
+    )
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) => DomainResult::Ok(r.rows_affected()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LINK IDENTITY
+/// =========================
+/// Resolves an external identity to its user.
+///
+/// A known `(provider, subject)` signs in its linked user. Otherwise the
+/// provider-verified email is matched against `users.email` and the
+/// identity is linked to that account, which counts as verifying it.
+/// Several accounts matching it is refused rather than guessed at.
+/// Accounts are never created here.
+pub async fn link_identity(
+    pool: &PgPool,
+    provider: &str,
+    claims: &IdentityClaims,
+) -> DomainResult<UserEntity, SsoError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ an identity linked earlier
+    let linked = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE user_identities i
+        SET last_login_at = CURRENT_TIMESTAMP
+        FROM users u
+        WHERE u.id = i.user_id
+          AND u.deleted_at IS NULL
+          AND i.provider = $1
+          AND i.subject = $2
+        RETURNING i.user_id
+        "#,
+    )
+    .bind(provider)
+    .bind(&claims.subject)
+    .fetch_optional(&mut *tx)
+    .await;
+
+    let user_id = match linked {
+        Ok(Some(user_id)) => user_id,
+        Ok(None) => {
+            // 2️⃣ otherwise link by verified email
+            let email = match &claims.email {
+                Some(email) if claims.email_verified => email,
+                _ => return DomainResult::Err(SsoError::EmailNotVerified),
+            };
+
+            // `users.email` is only unique as written, so case variants of
+            // one address may belong to different accounts
+            let user_id = match sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT id FROM users
+                WHERE LOWER(email) = LOWER($1) AND deleted_at IS NULL
+                LIMIT 2
+                "#,
+            )
+            .bind(email)
+            .fetch_all(&mut *tx)
+            .await
+            {
+                Ok(ids) => match ids.as_slice() {
+                    [id] => *id,
+                    [] => return DomainResult::Err(SsoError::NoMatchingAccount),
+                    _ => return DomainResult::Err(SsoError::AmbiguousAccount),
+                },
+                Err(e) => return DomainResult::Err(e.into()),
+            };
+
+            let res = sqlx::query(
+                r#"
+                INSERT INTO user_identities (user_id, provider, subject, email, last_login_at)
+                VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP)
+                "#,
+            )
+            .bind(user_id)
+            .bind(provider)
+            .bind(&claims.subject)
+            .bind(email)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = res {
+                return DomainResult::Err(e.into());
+            }
+
+            // the provider vouched for the address
+            let res = sqlx::query(
+                "UPDATE users SET verified_at = CURRENT_TIMESTAMP, version = version + 1 WHERE id = $1 AND verified_at IS NULL",
+            )
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await;
+
+            if let Err(e) = res {
+                return DomainResult::Err(e.into());
+            }
+
+            user_id
+        }
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 3️⃣ load the user
+    let user = match sqlx::query_as::<_, UserEntity>(
+        r#"
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
+        FROM users
+        WHERE id = $1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match tx.commit().await {
+        Ok(_) => DomainResult::Ok(user),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
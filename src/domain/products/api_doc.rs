@@ -0,0 +1,18 @@
+use utoipa::OpenApi;
+
+use super::dtos::create::CreateProductRequest;
+use super::dtos::response::ProductResponse;
+use super::dtos::update::UpdateProductRequest;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::get_all_products,
+        super::handlers::create_product,
+        super::handlers::find_one_product,
+        super::handlers::update_product,
+        super::handlers::delete_product
+    ),
+    components(schemas(ProductResponse, CreateProductRequest, UpdateProductRequest))
+)]
+pub struct ProductsApi;
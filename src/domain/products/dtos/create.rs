@@ -0,0 +1,33 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateProductRequest {
+    pub name: String,
+    /// Decimal string, e.g. `"199.50"`
+    pub price: Decimal,
+}
+
+impl Validate for CreateProductRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("name", &self.name)
+            .length("name", &self.name, 0, 255);
+        validate_price(v, self.price);
+    }
+}
+
+/// `price` is `DECIMAL(10, 2)` and must not be negative
+pub fn validate_price(v: &mut Validator, price: Decimal) {
+    if price.is_sign_negative() {
+        v.add("price", "negative", "must not be negative");
+    }
+    if price.scale() > 2 {
+        v.add("price", "too_precise", "must have at most 2 decimal places");
+    }
+    if price >= Decimal::new(100_000_000, 0) {
+        v.add("price", "out_of_range", "must be below 100000000");
+    }
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::products::entities::ProductEntity;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProductResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub price: Decimal,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ProductEntity> for ProductResponse {
+    fn from(product: ProductEntity) -> Self {
+        Self {
+            id: product.id,
+            name: product.name,
+            price: product.price,
+            created_at: product.created_at,
+            updated_at: product.updated_at,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::create::validate_price;
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateProductRequest {
+    pub name: Option<String>,
+    pub price: Option<Decimal>,
+}
+
+impl Validate for UpdateProductRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            v.required("name", name).length("name", name, 0, 255);
+        }
+        if let Some(price) = self.price {
+            validate_price(v, price);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod product_entity;
+
+pub use product_entity::ProductEntity;
@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub struct ProductEntity {
+    pub id: Uuid,
+    pub name: String,
+    pub price: Decimal, // DECIMAL(10, 2)
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
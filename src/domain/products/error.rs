@@ -0,0 +1,48 @@
+use std::fmt;
+
+use crate::shared::db_error::DbError;
+use crate::shared::error::AppError;
+
+/// Failures of the product usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum ProductError {
+    NegativePrice,
+    /// Doesn't fit `DECIMAL(10, 2)`
+    PriceOutOfRange,
+    /// `name` longer than 255 characters
+    NameTooLong,
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for ProductError {
+    fn from(err: sqlx::Error) -> Self {
+        match DbError::from(err) {
+            db if db.is_check_violation("products_price_non_negative") => {
+                ProductError::NegativePrice
+            }
+            DbError::NumericOutOfRange => ProductError::PriceOutOfRange,
+            DbError::ValueTooLong => ProductError::NameTooLong,
+            db => ProductError::Db(db),
+        }
+    }
+}
+
+impl fmt::Display for ProductError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProductError::NegativePrice => write!(f, "price must not be negative"),
+            ProductError::PriceOutOfRange => write!(f, "price must be below 100000000"),
+            ProductError::NameTooLong => write!(f, "name must be at most 255 characters"),
+            ProductError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<ProductError> for AppError {
+    fn from(err: ProductError) -> Self {
+        match err {
+            ProductError::Db(db) => db.into(),
+            _ => AppError::bad_request(err.to_string()),
+        }
+    }
+}
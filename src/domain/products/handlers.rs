@@ -0,0 +1,150 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::dtos::create::CreateProductRequest;
+use super::dtos::response::ProductResponse;
+use super::dtos::update::UpdateProductRequest;
+use super::query::ListProductsQuery;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::shared::pagination::{PaginationMeta, clamp_per_page};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    get,
+    path = "",
+    params(ListProductsQuery),
+    responses(
+        (status = 200, description = "Get a page of products", body = [ProductResponse]),
+        (status = 400, description = "min_price is greater than max_price"),
+        (status = 403, description = "Missing products:read permission")
+    )
+)]
+pub async fn get_all_products(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListProductsQuery>,
+) -> Result<ApiResponse<Vec<ProductResponse>>, AppError> {
+    if let (Some(min), Some(max)) = (query.min_price, query.max_price)
+        && min > max
+    {
+        return Err(AppError::bad_request(
+            "min_price must not be greater than max_price",
+        ));
+    }
+
+    match usecases::get_all_products(&state.db, &query).await {
+        DomainResult::Ok(page) => {
+            let page = page.map(ProductResponse::from);
+            let meta = PaginationMeta {
+                total: page.total,
+                page: Some(query.page.unwrap_or(1).max(1)),
+                per_page: clamp_per_page(query.per_page),
+                next_cursor: page.next_cursor,
+            };
+            Ok(ApiResponse::paginated(page.items, meta))
+        }
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    responses(
+        (status = 200, description = "Get product by ID", body = ProductResponse),
+        (status = 403, description = "Missing products:read permission"),
+        (status = 404, description = "Product not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Product ID")
+    )
+)]
+pub async fn find_one_product(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<ProductResponse>, AppError> {
+    match usecases::find_one_product(&state.db, id).await {
+        DomainResult::Ok(product) => Ok(ApiResponse::ok(product.into())),
+        DomainResult::NotFound => Err(AppError::not_found("Product not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = CreateProductRequest,
+    responses(
+        (status = 201, description = "Create new product", body = ProductResponse),
+        (status = 400, description = "Price negative or out of range"),
+        (status = 403, description = "Missing products:write permission"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn create_product(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<CreateProductRequest>,
+) -> Result<ApiResponse<ProductResponse>, AppError> {
+    match usecases::create_product(&state.db, req).await {
+        DomainResult::Ok(product) => Ok(ApiResponse::created(product.into())),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    request_body = UpdateProductRequest,
+    responses(
+        (status = 200, description = "Update product", body = ProductResponse),
+        (status = 403, description = "Missing products:write permission"),
+        (status = 404, description = "Product not found"),
+        (status = 422, description = "Request body failed validation")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Product ID")
+    )
+)]
+pub async fn update_product(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(req): ValidatedJson<UpdateProductRequest>,
+) -> Result<ApiResponse<ProductResponse>, AppError> {
+    match usecases::update_product(&state.db, id, req).await {
+        DomainResult::Ok(product) => Ok(ApiResponse::ok(product.into())),
+        DomainResult::NotFound => Err(AppError::not_found("Product not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    responses(
+        (status = 200, description = "Delete product"),
+        (status = 403, description = "Missing products:delete permission"),
+        (status = 404, description = "Product not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "Product ID")
+    )
+)]
+pub async fn delete_product(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::delete_product(&state.db, id).await {
+        DomainResult::Ok(_) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("Product not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
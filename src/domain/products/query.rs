@@ -0,0 +1,45 @@
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::shared::pagination::SortOrder;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSortField {
+    #[default]
+    CreatedAt,
+    Name,
+    Price,
+}
+
+impl ProductSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            ProductSortField::CreatedAt => "created_at",
+            ProductSortField::Name => "name",
+            ProductSortField::Price => "price",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListProductsQuery {
+    /// 1-based page number
+    pub page: Option<u32>,
+    /// Items per page (1-100, default 20)
+    pub per_page: Option<u32>,
+    /// Case-insensitive substring match on name
+    pub q: Option<String>,
+    /// Inclusive lower price bound
+    pub min_price: Option<Decimal>,
+    /// Inclusive upper price bound
+    pub max_price: Option<Decimal>,
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: ProductSortField,
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrder,
+}
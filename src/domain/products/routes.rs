@@ -0,0 +1,36 @@
+use axum::Router;
+use axum::routing::{delete, get, post, put};
+
+use super::handlers;
+use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::RequirePermission;
+
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::get_all_products)
+                .route_layer(RequirePermission::new(state, Permission::ProductsRead)),
+        )
+        .route(
+            "/",
+            post(handlers::create_product)
+                .route_layer(RequirePermission::new(state, Permission::ProductsWrite)),
+        )
+        .route(
+            "/{id}",
+            get(handlers::find_one_product)
+                .route_layer(RequirePermission::new(state, Permission::ProductsRead)),
+        )
+        .route(
+            "/{id}",
+            put(handlers::update_product)
+                .route_layer(RequirePermission::new(state, Permission::ProductsWrite)),
+        )
+        .route(
+            "/{id}",
+            delete(handlers::delete_product)
+                .route_layer(RequirePermission::new(state, Permission::ProductsDelete)),
+        )
+}
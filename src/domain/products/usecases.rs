@@ -0,0 +1,181 @@
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::error;
+use uuid::Uuid;
+
+use super::dtos::create::CreateProductRequest;
+use super::dtos::update::UpdateProductRequest;
+use super::entities::ProductEntity;
+use super::error::ProductError;
+use super::query::ListProductsQuery;
+use crate::shared::pagination::{Page, clamp_per_page, escape_like};
+use crate::shared::types::result::DomainResult;
+
+/// =========================
+/// GET ALL PRODUCTS
+/// =========================
+pub async fn get_all_products(
+    pool: &PgPool,
+    query: &ListProductsQuery,
+) -> DomainResult<Page<ProductEntity>, ProductError> {
+    let per_page = clamp_per_page(query.per_page);
+    let offset = (query.page.unwrap_or(1).max(1) as i64 - 1) * per_page as i64;
+
+    let mut count_query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM products");
+    push_product_filters(&mut count_query, query);
+
+    let total = match count_query
+        .build_query_scalar::<i64>()
+        .fetch_one(pool)
+        .await
+    {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let mut page_query = QueryBuilder::<Postgres>::new(
+        "SELECT id, name, price, created_at, updated_at FROM products",
+    );
+    push_product_filters(&mut page_query, query);
+    page_query
+        .push(format!(
+            " ORDER BY {col} {dir}, id {dir} LIMIT ",
+            col = query.sort.column(),
+            dir = query.order.as_sql()
+        ))
+        .push_bind(per_page as i64)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    match page_query
+        .build_query_as::<ProductEntity>()
+        .fetch_all(pool)
+        .await
+    {
+        Ok(items) => DomainResult::Ok(Page {
+            items,
+            total,
+            next_cursor: None,
+        }),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// FIND ONE PRODUCT
+/// =========================
+pub async fn find_one_product(
+    pool: &PgPool,
+    id: Uuid,
+) -> DomainResult<ProductEntity, ProductError> {
+    let product = sqlx::query_as::<_, ProductEntity>(
+        r#"
+        SELECT id, name, price, created_at, updated_at
+        FROM products
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await;
+
+    match product {
+        Ok(Some(p)) => DomainResult::Ok(p),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// CREATE PRODUCT
+/// =========================
+pub async fn create_product(
+    pool: &PgPool,
+    req: CreateProductRequest,
+) -> DomainResult<ProductEntity, ProductError> {
+    let product = sqlx::query_as::<_, ProductEntity>(
+        r#"
+        INSERT INTO products (name, price)
+        VALUES ($1, $2)
+        RETURNING id, name, price, created_at, updated_at
+        "#,
+    )
+    .bind(req.name)
+    .bind(req.price)
+    .fetch_one(pool)
+    .await;
+
+    match product {
+        Ok(p) => DomainResult::Ok(p),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// UPDATE PRODUCT
+/// =========================
+pub async fn update_product(
+    pool: &PgPool,
+    id: Uuid,
+    req: UpdateProductRequest,
+) -> DomainResult<ProductEntity, ProductError> {
+    let product = sqlx::query_as::<_, ProductEntity>(
+        r#"
+        UPDATE products
+        SET
+            name = COALESCE($1, name),
+            price = COALESCE($2, price),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        RETURNING id, name, price, created_at, updated_at
+        "#,
+    )
+    .bind(req.name)
+    .bind(req.price)
+    .bind(id)
+    .fetch_optional(pool)
+    .await;
+
+    match product {
+        Ok(Some(p)) => DomainResult::Ok(p),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// DELETE PRODUCT
+/// =========================
+pub async fn delete_product(pool: &PgPool, id: Uuid) -> DomainResult<(), ProductError> {
+    let result = sqlx::query("DELETE FROM products WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => {
+            error!("Error deleting product: {:?}", e);
+            DomainResult::Err(e.into())
+        }
+    }
+}
+
+/// =========================
+/// LIST FILTERS
+/// =========================
+fn push_product_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ListProductsQuery) {
+    builder.push(" WHERE TRUE");
+
+    if let Some(q) = &query.q {
+        builder
+            .push(" AND name ILIKE ")
+            .push_bind(format!("%{}%", escape_like(q)));
+    }
+    if let Some(min) = query.min_price {
+        builder.push(" AND price >= ").push_bind(min);
+    }
+    if let Some(max) = query.max_price {
+        builder.push(" AND price <= ").push_bind(max);
+    }
+}
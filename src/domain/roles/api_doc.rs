@@ -0,0 +1,15 @@
+use utoipa::OpenApi;
+
+use super::dtos::response::UserRoleResponse;
+use super::entities::Role;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::list_user_roles,
+        super::handlers::assign_role,
+        super::handlers::revoke_role
+    ),
+    components(schemas(UserRoleResponse, Role))
+)]
+pub struct RolesApi;
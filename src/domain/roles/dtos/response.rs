@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::roles::entities::Role;
+use crate::domain::roles::entities::user_role_entity::UserRoleEntity;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserRoleResponse {
+    pub user_id: Uuid,
+    pub role: Role,
+    pub assigned_at: NaiveDateTime,
+}
+
+impl TryFrom<UserRoleEntity> for UserRoleResponse {
+    type Error = String;
+
+    fn try_from(entity: UserRoleEntity) -> Result<Self, Self::Error> {
+        Ok(Self {
+            user_id: entity.user_id,
+            role: entity.role.parse()?,
+            assigned_at: entity.assigned_at,
+        })
+    }
+}
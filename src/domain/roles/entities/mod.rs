@@ -0,0 +1,6 @@
+pub mod permission;
+pub mod role;
+pub mod user_role_entity;
+
+pub use permission::Permission;
+pub use role::Role;
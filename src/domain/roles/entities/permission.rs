@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+/// Permissions granted to roles through the `role_permissions` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    UsersRead,
+    UsersWrite,
+    UsersDelete,
+    UsersResetTwoFactor,
+    UsersUnlock,
+    RolesManage,
+    SchoolsRead,
+    SchoolsWrite,
+    SchoolsDelete,
+    ProductsRead,
+    ProductsWrite,
+    ProductsDelete,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 12] = [
+        Permission::UsersRead,
+        Permission::UsersWrite,
+        Permission::UsersDelete,
+        Permission::UsersResetTwoFactor,
+        Permission::UsersUnlock,
+        Permission::RolesManage,
+        Permission::SchoolsRead,
+        Permission::SchoolsWrite,
+        Permission::SchoolsDelete,
+        Permission::ProductsRead,
+        Permission::ProductsWrite,
+        Permission::ProductsDelete,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::UsersRead => "users:read",
+            Permission::UsersWrite => "users:write",
+            Permission::UsersDelete => "users:delete",
+            Permission::UsersResetTwoFactor => "users:2fa:reset",
+            Permission::UsersUnlock => "users:unlock",
+            Permission::RolesManage => "roles:manage",
+            Permission::SchoolsRead => "schools:read",
+            Permission::SchoolsWrite => "schools:write",
+            Permission::SchoolsDelete => "schools:delete",
+            Permission::ProductsRead => "products:read",
+            Permission::ProductsWrite => "products:write",
+            Permission::ProductsDelete => "products:delete",
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| format!("unknown permission `{}`", s))
+    }
+}
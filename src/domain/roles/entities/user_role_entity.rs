@@ -0,0 +1,10 @@
+use chrono::NaiveDateTime;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub struct UserRoleEntity {
+    pub user_id: Uuid,
+    pub role: String,
+    pub assigned_at: NaiveDateTime,
+}
@@ -0,0 +1,123 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tower::{Layer, Service};
+
+use super::entities::Permission;
+use super::usecases::{self, Authorization};
+use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::shared::error::AppError;
+use crate::shared::types::result::DomainResult;
+
+/// Route layer rejecting callers whose roles don't grant `permission`.
+///
+/// Responds `401` without valid credentials and `403` when the permission
+/// is missing (from the user's roles or the API key's scopes), or while
+/// the caller holds a role that requires two-factor authentication
+/// without having enabled it. On success the resolved `AuthUser` is
+/// stored in the request extensions.
+#[derive(Clone)]
+pub struct RequirePermission {
+    state: AppState,
+    permission: Permission,
+}
+
+impl RequirePermission {
+    pub fn new(state: &AppState, permission: Permission) -> Self {
+        Self {
+            state: state.clone(),
+            permission,
+        }
+    }
+}
+
+impl<S> Layer<S> for RequirePermission {
+    type Service = RequirePermissionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequirePermissionService {
+            inner,
+            state: self.state.clone(),
+            permission: self.permission,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequirePermissionService<S> {
+    inner: S,
+    state: AppState,
+    permission: Permission,
+}
+
+impl<S> Service<Request> for RequirePermissionService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        // take the service that was driven to readiness, leave a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = self.state.clone();
+        let permission = self.permission;
+
+        Box::pin(async move {
+            let (mut parts, body) = req.into_parts();
+
+            let user = match AuthUser::from_request_parts(&mut parts, &state).await {
+                Ok(user) => user,
+                Err(e) => return Ok(e.into_response()),
+            };
+
+            if let Err(e) = require_permission(&state, &user, permission).await {
+                return Ok(e.into_response());
+            }
+
+            parts.extensions.insert(user);
+            inner.call(Request::from_parts(parts, body)).await
+        })
+    }
+}
+
+/// What [`RequirePermission`] checks, for handlers whose permission depends
+/// on the request (e.g. acting on someone else's resources)
+pub async fn require_permission(
+    state: &AppState,
+    user: &AuthUser,
+    permission: Permission,
+) -> Result<(), AppError> {
+    match usecases::authorize(
+        &state.db,
+        user,
+        permission,
+        &state.config.auth.two_factor_required_roles,
+    )
+    .await
+    {
+        DomainResult::Ok(Authorization::Granted) => Ok(()),
+        DomainResult::Ok(Authorization::Denied) | DomainResult::NotFound => {
+            Err(AppError::Forbidden)
+        }
+        DomainResult::Ok(Authorization::TwoFactorRequired) => Err(AppError::problem(
+            StatusCode::FORBIDDEN,
+            "two_factor_required",
+            "Enable two-factor authentication to use this endpoint",
+        )),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,90 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::dtos::response::UserRoleResponse;
+use super::entities::Role;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::shared::error::AppError;
+use crate::shared::extractors::ValidatedPath;
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses(
+        (status = 200, description = "List roles assigned to a user", body = [UserRoleResponse]),
+        (status = 403, description = "Missing roles:manage permission"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn list_user_roles(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<Vec<UserRoleResponse>>, AppError> {
+    match usecases::list_user_roles(&state.db, id).await {
+        DomainResult::Ok(roles) => Ok(ApiResponse::ok(
+            roles
+                .into_iter()
+                .map(UserRoleResponse::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(AppError::internal_server_error)?,
+        )),
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/{role}",
+    responses(
+        (status = 200, description = "Assign role to user", body = UserRoleResponse),
+        (status = 403, description = "Missing roles:manage permission"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        ("role" = Role, Path, description = "Role name")
+    )
+)]
+pub async fn assign_role(
+    State(state): State<AppState>,
+    ValidatedPath((id, role)): ValidatedPath<(Uuid, Role)>,
+) -> Result<ApiResponse<UserRoleResponse>, AppError> {
+    match usecases::assign_role(&state.db, id, role).await {
+        DomainResult::Ok(assigned) => Ok(ApiResponse::ok(
+            UserRoleResponse::try_from(assigned).map_err(AppError::internal_server_error)?,
+        )),
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{role}",
+    responses(
+        (status = 200, description = "Revoke role from user"),
+        (status = 403, description = "Missing roles:manage permission"),
+        (status = 404, description = "Role not assigned")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        ("role" = Role, Path, description = "Role name")
+    )
+)]
+pub async fn revoke_role(
+    State(state): State<AppState>,
+    ValidatedPath((id, role)): ValidatedPath<(Uuid, Role)>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::revoke_role(&state.db, id, role).await {
+        DomainResult::Ok(_) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("Role not assigned")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,7 @@
+pub mod api_doc;
+pub mod dtos;
+pub mod entities;
+pub mod guard;
+pub mod handlers;
+pub mod routes;
+pub mod usecases;
@@ -0,0 +1,17 @@
+use axum::Router;
+use axum::routing::{get, put};
+
+use super::entities::Permission;
+use super::guard::RequirePermission;
+use super::handlers;
+use crate::app::state::AppState;
+
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(handlers::list_user_roles))
+        .route(
+            "/{role}",
+            put(handlers::assign_role).delete(handlers::revoke_role),
+        )
+        .route_layer(RequirePermission::new(state, Permission::RolesManage))
+}
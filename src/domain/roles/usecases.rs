@@ -0,0 +1,202 @@
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+use super::entities::user_role_entity::UserRoleEntity;
+use super::entities::{Permission, Role};
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::two_factor;
+use crate::shared::db_error::DbError;
+use crate::shared::types::result::DomainResult;
+
+/// Outcome of [`authorize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Authorization {
+    Granted,
+    /// Not granted by the user's roles, or outside the API key's scopes
+    Denied,
+    /// Granted, but the user holds a role in `two_factor_required_roles`
+    /// without having enabled two-factor authentication
+    TwoFactorRequired,
+}
+
+/// =========================
+/// AUTHORIZE
+/// =========================
+/// Every check standing between a caller and `permission`: the API key's
+/// scopes, the user's roles and the two-factor requirement of those roles.
+pub async fn authorize(
+    pool: &PgPool,
+    user: &AuthUser,
+    permission: Permission,
+    two_factor_required_roles: &[String],
+) -> DomainResult<Authorization, DbError> {
+    if !user.allows(permission) {
+        return DomainResult::Ok(Authorization::Denied);
+    }
+
+    match has_permission(pool, user.id, permission).await {
+        DomainResult::Ok(true) => {}
+        DomainResult::Ok(false) | DomainResult::NotFound => {
+            return DomainResult::Ok(Authorization::Denied);
+        }
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    }
+
+    if two_factor_required_roles.is_empty() {
+        return DomainResult::Ok(Authorization::Granted);
+    }
+
+    match two_factor::usecases::is_missing_required(pool, user.id, two_factor_required_roles).await
+    {
+        DomainResult::Ok(true) => DomainResult::Ok(Authorization::TwoFactorRequired),
+        DomainResult::Ok(false) | DomainResult::NotFound => {
+            DomainResult::Ok(Authorization::Granted)
+        }
+        DomainResult::Err(e) => DomainResult::Err(e),
+    }
+}
+
+/// =========================
+/// HAS PERMISSION
+/// =========================
+/// Soft-deleted users hold no permissions
+pub async fn has_permission(
+    pool: &PgPool,
+    user_id: Uuid,
+    permission: Permission,
+) -> DomainResult<bool, DbError> {
+    let granted = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM user_roles ur
+            JOIN role_permissions rp ON rp.role = ur.role
+            JOIN users u ON u.id = ur.user_id AND u.deleted_at IS NULL
+            WHERE ur.user_id = $1 AND rp.permission = $2
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(permission.as_str())
+    .fetch_one(pool)
+    .await;
+
+    match granted {
+        Ok(g) => DomainResult::Ok(g),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST USER ROLES
+/// =========================
+pub async fn list_user_roles(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> DomainResult<Vec<UserRoleEntity>, DbError> {
+    match user_exists(pool, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    let roles = sqlx::query_as::<_, UserRoleEntity>(
+        r#"
+        SELECT user_id, role, assigned_at
+        FROM user_roles
+        WHERE user_id = $1
+        ORDER BY assigned_at
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await;
+
+    match roles {
+        Ok(r) => DomainResult::Ok(r),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// ASSIGN ROLE
+/// =========================
+/// Idempotent: assigning a role the user already holds returns the existing row.
+pub async fn assign_role(
+    pool: &PgPool,
+    user_id: Uuid,
+    role: Role,
+) -> DomainResult<UserRoleEntity, DbError> {
+    match user_exists(pool, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    let assigned = sqlx::query_as::<_, UserRoleEntity>(
+        r#"
+        INSERT INTO user_roles (user_id, role)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, role) DO UPDATE SET role = EXCLUDED.role
+        RETURNING user_id, role, assigned_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(role.as_str())
+    .fetch_one(pool)
+    .await;
+
+    match assigned {
+        Ok(r) => DomainResult::Ok(r),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// REVOKE ROLE
+/// =========================
+pub async fn revoke_role(pool: &PgPool, user_id: Uuid, role: Role) -> DomainResult<(), DbError> {
+    let result = sqlx::query("DELETE FROM user_roles WHERE user_id = $1 AND role = $2")
+        .bind(user_id)
+        .bind(role.as_str())
+        .execute(pool)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// BOOTSTRAP ADMIN
+/// =========================
+/// Grants `admin` to the configured account on startup so a fresh
+/// deployment has someone able to manage roles.
+pub async fn bootstrap_admin(pool: &PgPool, email: &str) -> Result<(), sqlx::Error> {
+    let granted = sqlx::query(
+        r#"
+        INSERT INTO user_roles (user_id, role)
+        SELECT id, 'admin' FROM users WHERE email = $1
+        ON CONFLICT (user_id, role) DO NOTHING
+        "#,
+    )
+    .bind(email)
+    .execute(pool)
+    .await?;
+
+    if granted.rows_affected() > 0 {
+        info!(email, "granted admin role to bootstrap account");
+    }
+
+    Ok(())
+}
+
+async fn user_exists(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users WHERE id = $1)")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+}
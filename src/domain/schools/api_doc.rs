@@ -0,0 +1,18 @@
+use utoipa::OpenApi;
+
+use super::dtos::create::CreateSchoolRequest;
+use super::dtos::response::SchoolResponse;
+use super::dtos::update::UpdateSchoolRequest;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::get_all_schools,
+        super::handlers::create_school,
+        super::handlers::find_one_school,
+        super::handlers::update_school,
+        super::handlers::delete_school
+    ),
+    components(schemas(SchoolResponse, CreateSchoolRequest, UpdateSchoolRequest))
+)]
+pub struct SchoolsApi;
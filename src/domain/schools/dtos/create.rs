@@ -1,4 +1,36 @@
-pub struct CreateSchoolDto {
-    pub name: String,
-    pub acronym: String,
+use crate::shared::types::hash::Hash;
+use crate::shared::validation::{Validate, Validator};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Longest BCP 47 tag we accept as a JSONB key
+pub const MAX_LOCALE_LEN: usize = 35;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateSchoolRequest {
+    pub name: Hash<String, String>,
+    pub acronym: Hash<String, String>,
+}
+
+impl Validate for CreateSchoolRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_translations(v, "name", &self.name, 255);
+        validate_translations(v, "acronym", &self.acronym, 32);
+    }
+}
+
+pub fn validate_translations(
+    v: &mut Validator,
+    field: &str,
+    values: &Hash<String, String>,
+    max_len: usize,
+) {
+    v.locales(
+        &format!("{}.values", field),
+        &values.values,
+        MAX_LOCALE_LEN,
+        |v, path, value| {
+            v.required(path, value).length(path, value, 0, max_len);
+        },
+    );
 }
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::schools::entities::School;
+use crate::shared::types::hash::Hash;
+use crate::shared::types::locale::Locale;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SchoolResponse {
+    pub id: Uuid,
+    pub name: Hash<String, String>,
+    pub acronym: Hash<String, String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<School> for SchoolResponse {
+    fn from(school: School) -> Self {
+        Self {
+            id: school.id,
+            name: school.name,
+            acronym: school.acronym,
+            created_at: school.created_at,
+            updated_at: school.updated_at,
+        }
+    }
+}
+
+impl SchoolResponse {
+    /// Narrow `name` and `acronym` to the best match for the requested locale
+    pub fn localized(mut self, locale: &Locale) -> Self {
+        self.name = self.name.localize(locale);
+        self.acronym = self.acronym.localize(locale);
+        self
+    }
+}
@@ -0,0 +1,22 @@
+use super::create::validate_translations;
+use crate::shared::types::hash::Hash;
+use crate::shared::validation::{Validate, Validator};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateSchoolRequest {
+    pub name: Option<Hash<String, String>>,
+    pub acronym: Option<Hash<String, String>>,
+}
+
+impl Validate for UpdateSchoolRequest {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(name) = &self.name {
+            validate_translations(v, "name", name, 255);
+        }
+        if let Some(acronym) = &self.acronym {
+            validate_translations(v, "acronym", acronym, 32);
+        }
+    }
+}
@@ -1 +1,4 @@
 pub mod school;
+pub mod school_entity;
+
+pub use school::School;
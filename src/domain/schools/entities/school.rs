@@ -1,8 +1,8 @@
 use crate::shared::types::hash::Hash;
-use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use chrono::NaiveDateTime;
 use uuid::Uuid;
-#[derive(Debug, Serialize, Deserialize, FromRow)]
+
+#[derive(Debug)]
 pub struct School {
     pub id: Uuid,
     pub name: Hash<String, String>,
@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use sqlx::FromRow;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::School;
+use crate::shared::types::hash::Hash;
+
+#[derive(Debug, FromRow)]
+pub struct SchoolEntity {
+    pub id: Uuid,
+    pub name: Json<HashMap<String, String>>, // {"th": "...", "en": "..."}
+    pub acronym: Json<HashMap<String, String>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<SchoolEntity> for School {
+    fn from(entity: SchoolEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: Hash::new(entity.name.0),
+            acronym: Hash::new(entity.acronym.0),
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
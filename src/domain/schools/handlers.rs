@@ -0,0 +1,150 @@
+use axum::extract::State;
+use uuid::Uuid;
+
+use super::dtos::create::CreateSchoolRequest;
+use super::dtos::response::SchoolResponse;
+use super::dtos::update::UpdateSchoolRequest;
+use super::query::ListSchoolsQuery;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{AcceptLocale, ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::shared::pagination::{PaginationMeta, clamp_per_page};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    get,
+    path = "",
+    params(
+        ListSchoolsQuery,
+        ("lang" = Option<String>, Query, description = "Preferred locales, e.g. `th,en`; `*` returns every translation"),
+        ("Accept-Language" = Option<String>, Header, description = "Used when `lang` is absent")
+    ),
+    responses(
+        (status = 200, description = "Get a page of schools", body = [SchoolResponse]),
+        (status = 403, description = "Missing schools:read permission")
+    )
+)]
+pub async fn get_all_schools(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListSchoolsQuery>,
+    AcceptLocale(locale): AcceptLocale,
+) -> Result<ApiResponse<Vec<SchoolResponse>>, AppError> {
+    match usecases::get_all_schools(&state.db, &query).await {
+        DomainResult::Ok(page) => {
+            let page = page.map(|school| SchoolResponse::from(school).localized(&locale));
+            let meta = PaginationMeta {
+                total: page.total,
+                page: Some(query.page.unwrap_or(1).max(1)),
+                per_page: clamp_per_page(query.per_page),
+                next_cursor: page.next_cursor,
+            };
+            Ok(ApiResponse::paginated(page.items, meta))
+        }
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    responses(
+        (status = 200, description = "Get school by ID", body = SchoolResponse),
+        (status = 403, description = "Missing schools:read permission"),
+        (status = 404, description = "School not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID"),
+        ("lang" = Option<String>, Query, description = "Preferred locales, e.g. `th,en`; `*` returns every translation"),
+        ("Accept-Language" = Option<String>, Header, description = "Used when `lang` is absent")
+    )
+)]
+pub async fn find_one_school(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    AcceptLocale(locale): AcceptLocale,
+) -> Result<ApiResponse<SchoolResponse>, AppError> {
+    match usecases::find_one_school(&state.db, id).await {
+        DomainResult::Ok(school) => Ok(ApiResponse::ok(
+            SchoolResponse::from(school).localized(&locale),
+        )),
+        DomainResult::NotFound => Err(AppError::not_found("School not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = CreateSchoolRequest,
+    responses(
+        (status = 201, description = "Create new school", body = SchoolResponse),
+        (status = 403, description = "Missing schools:write permission"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn create_school(
+    State(state): State<AppState>,
+    ValidatedJson(req): ValidatedJson<CreateSchoolRequest>,
+) -> Result<ApiResponse<SchoolResponse>, AppError> {
+    match usecases::create_school(&state.db, req).await {
+        DomainResult::Ok(school) => Ok(ApiResponse::created(school.into())),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    request_body = UpdateSchoolRequest,
+    responses(
+        (status = 200, description = "Update school", body = SchoolResponse),
+        (status = 403, description = "Missing schools:write permission"),
+        (status = 404, description = "School not found"),
+        (status = 422, description = "Request body failed validation")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    )
+)]
+pub async fn update_school(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(req): ValidatedJson<UpdateSchoolRequest>,
+) -> Result<ApiResponse<SchoolResponse>, AppError> {
+    match usecases::update_school(&state.db, id, req).await {
+        DomainResult::Ok(school) => Ok(ApiResponse::ok(school.into())),
+        DomainResult::NotFound => Err(AppError::not_found("School not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    responses(
+        (status = 200, description = "Delete school"),
+        (status = 403, description = "Missing schools:delete permission"),
+        (status = 404, description = "School not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "School ID")
+    )
+)]
+pub async fn delete_school(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::delete_school(&state.db, id).await {
+        DomainResult::Ok(_) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("School not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListSchoolsQuery {
+    /// 1-based page number
+    pub page: Option<u32>,
+    /// Items per page (1-100, default 20)
+    pub per_page: Option<u32>,
+    /// Case-insensitive substring match on any translation of name or acronym
+    pub q: Option<String>,
+}
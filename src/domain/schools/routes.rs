@@ -0,0 +1,36 @@
+use axum::Router;
+use axum::routing::{delete, get, post, put};
+
+use super::handlers;
+use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::RequirePermission;
+
+pub fn router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(handlers::get_all_schools)
+                .route_layer(RequirePermission::new(state, Permission::SchoolsRead)),
+        )
+        .route(
+            "/",
+            post(handlers::create_school)
+                .route_layer(RequirePermission::new(state, Permission::SchoolsWrite)),
+        )
+        .route(
+            "/{id}",
+            get(handlers::find_one_school)
+                .route_layer(RequirePermission::new(state, Permission::SchoolsRead)),
+        )
+        .route(
+            "/{id}",
+            put(handlers::update_school)
+                .route_layer(RequirePermission::new(state, Permission::SchoolsWrite)),
+        )
+        .route(
+            "/{id}",
+            delete(handlers::delete_school)
+                .route_layer(RequirePermission::new(state, Permission::SchoolsDelete)),
+        )
+}
@@ -0,0 +1,174 @@
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use tracing::error;
+use uuid::Uuid;
+
+use super::dtos::create::CreateSchoolRequest;
+use super::dtos::update::UpdateSchoolRequest;
+use super::entities::School;
+use super::entities::school_entity::SchoolEntity;
+use super::query::ListSchoolsQuery;
+use crate::shared::db_error::DbError;
+use crate::shared::pagination::{Page, clamp_per_page, escape_like};
+use crate::shared::types::result::DomainResult;
+
+/// =========================
+/// GET ALL SCHOOLS
+/// =========================
+pub async fn get_all_schools(
+    pool: &PgPool,
+    query: &ListSchoolsQuery,
+) -> DomainResult<Page<School>, DbError> {
+    let per_page = clamp_per_page(query.per_page);
+    let offset = (query.page.unwrap_or(1).max(1) as i64 - 1) * per_page as i64;
+
+    let mut count_query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM schools");
+    push_school_filters(&mut count_query, query);
+
+    let total = match count_query
+        .build_query_scalar::<i64>()
+        .fetch_one(pool)
+        .await
+    {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let mut page_query = QueryBuilder::<Postgres>::new(
+        "SELECT id, name, acronym, created_at, updated_at FROM schools",
+    );
+    push_school_filters(&mut page_query, query);
+    page_query
+        .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(per_page as i64)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    match page_query
+        .build_query_as::<SchoolEntity>()
+        .fetch_all(pool)
+        .await
+    {
+        Ok(schools) => DomainResult::Ok(Page {
+            items: schools.into_iter().map(School::from).collect(),
+            total,
+            next_cursor: None,
+        }),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// FIND ONE SCHOOL
+/// =========================
+pub async fn find_one_school(pool: &PgPool, id: Uuid) -> DomainResult<School, DbError> {
+    let school = sqlx::query_as::<_, SchoolEntity>(
+        r#"
+        SELECT id, name, acronym, created_at, updated_at
+        FROM schools
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await;
+
+    match school {
+        Ok(Some(s)) => DomainResult::Ok(s.into()),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// CREATE SCHOOL
+/// =========================
+pub async fn create_school(
+    pool: &PgPool,
+    req: CreateSchoolRequest,
+) -> DomainResult<School, DbError> {
+    let school = sqlx::query_as::<_, SchoolEntity>(
+        r#"
+        INSERT INTO schools (name, acronym)
+        VALUES ($1, $2)
+        RETURNING id, name, acronym, created_at, updated_at
+        "#,
+    )
+    .bind(Json(&req.name.values))
+    .bind(Json(&req.acronym.values))
+    .fetch_one(pool)
+    .await;
+
+    match school {
+        Ok(s) => DomainResult::Ok(s.into()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// UPDATE SCHOOL
+/// =========================
+pub async fn update_school(
+    pool: &PgPool,
+    id: Uuid,
+    req: UpdateSchoolRequest,
+) -> DomainResult<School, DbError> {
+    let school = sqlx::query_as::<_, SchoolEntity>(
+        r#"
+        UPDATE schools
+        SET
+            name = COALESCE($1, name),
+            acronym = COALESCE($2, acronym),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        RETURNING id, name, acronym, created_at, updated_at
+        "#,
+    )
+    .bind(req.name.as_ref().map(|n| Json(&n.values)))
+    .bind(req.acronym.as_ref().map(|a| Json(&a.values)))
+    .bind(id)
+    .fetch_optional(pool)
+    .await;
+
+    match school {
+        Ok(Some(s)) => DomainResult::Ok(s.into()),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// DELETE SCHOOL
+/// =========================
+pub async fn delete_school(pool: &PgPool, id: Uuid) -> DomainResult<(), DbError> {
+    let result = sqlx::query("DELETE FROM schools WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await;
+
+    match result {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => {
+            error!("Error deleting school: {:?}", e);
+            DomainResult::Err(e.into())
+        }
+    }
+}
+
+/// =========================
+/// LIST FILTERS
+/// =========================
+fn push_school_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ListSchoolsQuery) {
+    builder.push(" WHERE TRUE");
+
+    if let Some(q) = &query.q {
+        let pattern = format!("%{}%", escape_like(q));
+        builder
+            .push(" AND (EXISTS (SELECT 1 FROM jsonb_each_text(name) WHERE value ILIKE ")
+            .push_bind(pattern.clone())
+            .push(") OR EXISTS (SELECT 1 FROM jsonb_each_text(acronym) WHERE value ILIKE ")
+            .push_bind(pattern)
+            .push("))");
+    }
+}
@@ -0,0 +1,10 @@
+use utoipa::OpenApi;
+
+use super::dtos::response::{SessionResponse, SessionStartedResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(super::handlers::list_sessions, super::handlers::revoke_session),
+    components(schemas(SessionResponse, SessionStartedResponse))
+)]
+pub struct SessionsApi;
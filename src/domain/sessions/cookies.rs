@@ -0,0 +1,43 @@
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use cookie::time::Duration;
+
+use crate::app::config::config::{CookieSameSite, Session};
+
+/// Session cookie (HttpOnly) plus a script-readable CSRF cookie, so a
+/// reloaded UI can recover the token it has to echo back
+pub fn session_cookies(config: &Session, token: String, csrf_token: String) -> CookieJar {
+    CookieJar::new()
+        .add(build(config, config.cookie_name.clone(), token, true))
+        .add(build(config, csrf_cookie_name(config), csrf_token, false))
+}
+
+/// Expires both cookies in the browser
+pub fn cleared_cookies(config: &Session) -> CookieJar {
+    let mut jar = CookieJar::new();
+    for (name, http_only) in [
+        (config.cookie_name.clone(), true),
+        (csrf_cookie_name(config), false),
+    ] {
+        let mut cookie = build(config, name, String::new(), http_only);
+        cookie.make_removal();
+        jar = jar.add(cookie);
+    }
+    jar
+}
+
+fn csrf_cookie_name(config: &Session) -> String {
+    format!("{}_csrf", config.cookie_name)
+}
+
+fn build(config: &Session, name: String, value: String, http_only: bool) -> Cookie<'static> {
+    Cookie::build((name, value))
+        .path("/")
+        .http_only(http_only)
+        .secure(config.cookie_secure)
+        .same_site(match config.same_site {
+            CookieSameSite::Strict => SameSite::Strict,
+            CookieSameSite::Lax => SameSite::Lax,
+        })
+        .max_age(Duration::seconds(config.absolute_timeout_secs))
+        .build()
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::sessions::entities::session_entity::SessionEntity;
+
+/// Returned by `/auth/login` when a cookie session was started
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionStartedResponse {
+    /// Echo as `X-CSRF-Token` on state-changing requests
+    pub csrf_token: String,
+    /// Seconds until the session ends regardless of activity
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    /// The session making this request
+    pub current: bool,
+}
+
+impl SessionResponse {
+    pub fn from_entity(session: SessionEntity, current: Option<Uuid>) -> Self {
+        Self {
+            current: current == Some(session.id),
+            id: session.id,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
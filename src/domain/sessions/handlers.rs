@@ -0,0 +1,78 @@
+use axum::extract::State;
+use axum_extra::extract::cookie::CookieJar;
+use uuid::Uuid;
+
+use super::cookies;
+use super::dtos::response::SessionResponse;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::shared::error::AppError;
+use crate::shared::extractors::ValidatedPath;
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses(
+        (status = 200, description = "List the caller's active sessions", body = [SessionResponse]),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys")
+    )
+)]
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<ApiResponse<Vec<SessionResponse>>, AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::list_sessions(&state.db, user.id, state.config.session.idle_timeout_secs).await
+    {
+        DomainResult::Ok(sessions) => Ok(ApiResponse::ok(
+            sessions
+                .into_iter()
+                .map(|s| SessionResponse::from_entity(s, user.session_id))
+                .collect(),
+        )),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{session_id}",
+    responses(
+        (status = 200, description = "Revoke a session, signing out the current one clears its cookies"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys, or missing CSRF token"),
+        (status = 404, description = "Session not found or already revoked")
+    ),
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    )
+)]
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedPath(session_id): ValidatedPath<Uuid>,
+) -> Result<(CookieJar, ApiResponse<()>), AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::revoke_session(&state.db, user.id, session_id).await {
+        DomainResult::Ok(()) if user.session_id == Some(session_id) => Ok((
+            cookies::cleared_cookies(&state.config.session),
+            ApiResponse::ok_empty(),
+        )),
+        DomainResult::Ok(()) => Ok((CookieJar::new(), ApiResponse::ok_empty())),
+        DomainResult::NotFound => Err(AppError::not_found("Session not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,11 @@
+use axum::Router;
+use axum::routing::{delete, get};
+
+use super::handlers;
+use crate::app::state::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(handlers::list_sessions))
+        .route("/{session_id}", delete(handlers::revoke_session))
+}
@@ -0,0 +1,159 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::entities::session_entity::SessionEntity;
+use crate::domain::auth::extractors::AuthUser;
+use crate::shared::db_error::DbError;
+use crate::shared::security::token::{generate_token, hash_token};
+use crate::shared::types::result::DomainResult;
+
+/// A freshly started session; the raw tokens are never stored
+pub struct NewSession {
+    pub session: SessionEntity,
+    pub token: String,
+    pub csrf_token: String,
+}
+
+/// =========================
+/// CREATE SESSION
+/// =========================
+pub async fn create_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    user_agent: Option<&str>,
+    ip: Option<&str>,
+    absolute_timeout_secs: i64,
+) -> DomainResult<NewSession, DbError> {
+    let token = generate_token();
+    let csrf_token = generate_token();
+
+    let session = sqlx::query_as::<_, SessionEntity>(
+        r#"
+        INSERT INTO sessions (user_id, token_hash, csrf_hash, user_agent, ip, expires_at)
+        VALUES ($1, $2, $3, $4, $5, CURRENT_TIMESTAMP + make_interval(secs => $6))
+        RETURNING id, user_id, user_agent, ip, created_at, last_seen_at, expires_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(hash_token(&token))
+    .bind(hash_token(&csrf_token))
+    // column is VARCHAR(255), agents can be longer
+    .bind(user_agent.map(|ua| ua.chars().take(255).collect::<String>()))
+    .bind(ip)
+    .bind(absolute_timeout_secs as f64)
+    .fetch_one(pool)
+    .await;
+
+    match session {
+        Ok(session) => DomainResult::Ok(NewSession {
+            session,
+            token,
+            csrf_token,
+        }),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// AUTHENTICATE
+/// =========================
+/// Resolves a session cookie to its user and slides the idle timeout.
+/// Unknown, revoked, idle and expired sessions yield `NotFound`.
+/// Returns the CSRF token hash alongside the user.
+pub async fn authenticate(
+    pool: &PgPool,
+    token: &str,
+    idle_timeout_secs: i64,
+) -> DomainResult<(AuthUser, String), DbError> {
+    let owner = sqlx::query_as::<_, (Uuid, Uuid, String, String)>(
+        r#"
+        UPDATE sessions s
+        SET last_seen_at = CURRENT_TIMESTAMP
+        FROM users u
+        WHERE u.id = s.user_id
+          AND u.deleted_at IS NULL
+          AND s.token_hash = $1
+          AND s.revoked_at IS NULL
+          AND s.expires_at > CURRENT_TIMESTAMP
+          AND s.last_seen_at > CURRENT_TIMESTAMP - make_interval(secs => $2)
+        RETURNING s.id, u.id, u.email, s.csrf_hash
+        "#,
+    )
+    .bind(hash_token(token))
+    .bind(idle_timeout_secs as f64)
+    .fetch_optional(pool)
+    .await;
+
+    match owner {
+        Ok(Some((session_id, id, email, csrf_hash))) => DomainResult::Ok((
+            AuthUser {
+                id,
+                email,
+                scopes: None,
+                session_id: Some(session_id),
+            },
+            csrf_hash,
+        )),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST SESSIONS
+/// =========================
+/// Only sessions that could still authenticate
+pub async fn list_sessions(
+    pool: &PgPool,
+    user_id: Uuid,
+    idle_timeout_secs: i64,
+) -> DomainResult<Vec<SessionEntity>, DbError> {
+    let sessions = sqlx::query_as::<_, SessionEntity>(
+        r#"
+        SELECT id, user_id, user_agent, ip, created_at, last_seen_at, expires_at
+        FROM sessions
+        WHERE user_id = $1
+          AND revoked_at IS NULL
+          AND expires_at > CURRENT_TIMESTAMP
+          AND last_seen_at > CURRENT_TIMESTAMP - make_interval(secs => $2)
+        ORDER BY last_seen_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .bind(idle_timeout_secs as f64)
+    .fetch_all(pool)
+    .await;
+
+    match sessions {
+        Ok(s) => DomainResult::Ok(s),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// REVOKE SESSION
+/// =========================
+/// Sessions of other users and already revoked ones yield `NotFound`
+pub async fn revoke_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> DomainResult<(), DbError> {
+    let res = sqlx::query(
+        r#"
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
@@ -0,0 +1,25 @@
+use utoipa::OpenApi;
+
+use super::dtos::challenge::{TwoFactorChallengeResponse, VerifyChallengeRequest};
+use super::dtos::enroll::{ConfirmEnrollmentRequest, EnrollmentResponse, RecoveryCodesResponse};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        super::handlers::enroll,
+        super::handlers::confirm,
+        super::handlers::verify
+    ),
+    components(schemas(
+        EnrollmentResponse,
+        ConfirmEnrollmentRequest,
+        RecoveryCodesResponse,
+        TwoFactorChallengeResponse,
+        VerifyChallengeRequest
+    ))
+)]
+pub struct TwoFactorApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(super::handlers::reset_user_two_factor))]
+pub struct TwoFactorAdminApi;
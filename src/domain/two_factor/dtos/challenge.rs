@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+/// Returned by `/auth/login` instead of tokens when 2FA is enabled
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorChallengeResponse {
+    pub two_factor_required: bool,
+    /// Partial-auth token, exchanged at `/auth/2fa/verify`
+    pub challenge_token: String,
+    pub expires_in: i64,
+}
+
+impl TwoFactorChallengeResponse {
+    pub fn new(challenge_token: String, expires_in: i64) -> Self {
+        Self {
+            two_factor_required: true,
+            challenge_token,
+            expires_in,
+        }
+    }
+}
+
+/// Exactly one of `code` and `recovery_code`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VerifyChallengeRequest {
+    pub challenge_token: String,
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+    /// Start a cookie session instead of issuing tokens
+    #[serde(default)]
+    pub session: bool,
+}
+
+impl Validate for VerifyChallengeRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("challenge_token", &self.challenge_token);
+
+        match (&self.code, &self.recovery_code) {
+            (Some(code), None) => {
+                v.required("code", code);
+            }
+            (None, Some(recovery_code)) => {
+                v.required("recovery_code", recovery_code);
+            }
+            (Some(_), Some(_)) => {
+                v.add(
+                    "recovery_code",
+                    "conflict",
+                    "provide either code or recovery_code, not both",
+                );
+            }
+            (None, None) => {
+                v.add("code", "required", "code or recovery_code is required");
+            }
+        }
+    }
+}
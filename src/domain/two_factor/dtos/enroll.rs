@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::shared::validation::{Validate, Validator};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EnrollmentResponse {
+    /// Base32 secret for manual entry
+    pub secret: String,
+    /// `otpauth://` URI, usually rendered as a QR code
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmEnrollmentRequest {
+    /// Current code from the authenticator app
+    pub code: String,
+}
+
+impl Validate for ConfirmEnrollmentRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("code", &self.code);
+    }
+}
+
+/// Shown once; only hashes are stored
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
@@ -0,0 +1,12 @@
+use chrono::NaiveDateTime;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow)]
+pub struct TwoFactorChallengeEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: NaiveDateTime,
+    pub attempts: i32,
+    pub consumed_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+use axum::http::StatusCode;
+
+use crate::shared::db_error::DbError;
+use crate::shared::error::AppError;
+
+/// Failures of the two-factor usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum TwoFactorError {
+    /// A confirmed authenticator is already registered
+    AlreadyEnabled,
+    /// No pending enrollment to confirm
+    NotEnrolled,
+    /// Wrong, reused or expired code
+    InvalidCode,
+    /// Secret or account name rejected by the TOTP library
+    Totp(String),
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for TwoFactorError {
+    fn from(err: sqlx::Error) -> Self {
+        TwoFactorError::Db(err.into())
+    }
+}
+
+impl fmt::Display for TwoFactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TwoFactorError::AlreadyEnabled => {
+                write!(f, "two-factor authentication already enabled")
+            }
+            TwoFactorError::NotEnrolled => write!(f, "no pending two-factor enrollment"),
+            TwoFactorError::InvalidCode => write!(f, "invalid two-factor code"),
+            TwoFactorError::Totp(msg) => write!(f, "totp setup failed: {}", msg),
+            TwoFactorError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<TwoFactorError> for AppError {
+    fn from(err: TwoFactorError) -> Self {
+        match err {
+            TwoFactorError::AlreadyEnabled => {
+                AppError::conflict("Two-factor authentication is already enabled")
+            }
+            TwoFactorError::NotEnrolled => AppError::problem(
+                StatusCode::CONFLICT,
+                "two_factor_not_enrolled",
+                "Start enrollment before confirming it",
+            ),
+            TwoFactorError::InvalidCode => AppError::problem(
+                StatusCode::BAD_REQUEST,
+                "invalid_two_factor_code",
+                "Invalid or already used code",
+            ),
+            TwoFactorError::Totp(msg) => AppError::internal_server_error(msg),
+            TwoFactorError::Db(db) => db.into(),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::header::USER_AGENT;
+use axum_extra::extract::cookie::CookieJar;
+use uuid::Uuid;
+
+use super::dtos::challenge::VerifyChallengeRequest;
+use super::dtos::enroll::{ConfirmEnrollmentRequest, EnrollmentResponse, RecoveryCodesResponse};
+use super::error::TwoFactorError;
+use super::usecases;
+use crate::app::state::AppState;
+use crate::domain::auth::dtos::login::LoginResponse;
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::auth::handlers::{check_login_throttle, complete_login, record_login_failure};
+use crate::domain::lockout;
+use crate::shared::error::AppError;
+use crate::shared::extractors::{ClientIp, ValidatedJson, ValidatedPath};
+use crate::shared::response::ApiResponse;
+use crate::shared::types::result::DomainResult;
+
+#[utoipa::path(
+    post,
+    path = "/enroll",
+    responses(
+        (status = 200, description = "Start enrollment, returns the secret and otpauth URI", body = EnrollmentResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys"),
+        (status = 409, description = "Two-factor authentication already enabled")
+    )
+)]
+pub async fn enroll(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<ApiResponse<EnrollmentResponse>, AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::enroll(
+        &state.db,
+        user.id,
+        &user.email,
+        &state.config.auth.totp_issuer,
+    )
+    .await
+    {
+        DomainResult::Ok((secret, otpauth_uri)) => Ok(ApiResponse::ok(EnrollmentResponse {
+            secret,
+            otpauth_uri,
+        })),
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/confirm",
+    request_body = ConfirmEnrollmentRequest,
+    responses(
+        (status = 200, description = "Enable two-factor authentication, returns one-time recovery codes", body = RecoveryCodesResponse),
+        (status = 400, description = "Invalid code"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys"),
+        (status = 409, description = "No pending enrollment, or already enabled"),
+        (status = 422, description = "Request body failed validation")
+    )
+)]
+pub async fn confirm(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(req): ValidatedJson<ConfirmEnrollmentRequest>,
+) -> Result<ApiResponse<RecoveryCodesResponse>, AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::confirm_enrollment(&state.db, user.id, &req.code).await {
+        DomainResult::Ok(recovery_codes) => {
+            Ok(ApiResponse::ok(RecoveryCodesResponse { recovery_codes }))
+        }
+        DomainResult::Err(e) => Err(e.into()),
+        _ => Err(AppError::internal_server_error(
+            "Unexpected error".to_string(),
+        )),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/verify",
+    request_body = VerifyChallengeRequest,
+    responses(
+        (status = 200, description = "Complete login, issue tokens or start a cookie session", body = LoginResponse),
+        (status = 400, description = "Invalid or already used code, or cookie sessions not enabled"),
+        (status = 401, description = "Challenge invalid, expired or out of attempts"),
+        (status = 422, description = "Request body failed validation"),
+        (status = 429, description = "Account or client locked after repeated failures, see Retry-After")
+    )
+)]
+pub async fn verify(
+    State(state): State<AppState>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    ValidatedJson(req): ValidatedJson<VerifyChallengeRequest>,
+) -> Result<(CookieJar, ApiResponse<LoginResponse>), AppError> {
+    if req.session && !state.config.session.enabled {
+        return Err(AppError::bad_request("Cookie sessions are not enabled"));
+    }
+
+    // 1️⃣ refuse locked accounts and clients, like the password step does
+    let account = match usecases::challenge_email(&state.db, &req.challenge_token).await {
+        DomainResult::Ok(email) => Some(lockout::usecases::account_key(&email)),
+        DomainResult::NotFound => None,
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    check_login_throttle(&state, account.as_deref(), ip.as_deref()).await?;
+
+    // 2️⃣ check the second factor, counting every bad code against the
+    // account too since each challenge has only a few attempts of its own
+    let user = match usecases::verify_challenge(
+        &state.db,
+        &req.challenge_token,
+        req.code.as_deref(),
+        req.recovery_code.as_deref(),
+    )
+    .await
+    {
+        DomainResult::Ok(user) => user,
+        DomainResult::NotFound => {
+            return Err(record_login_failure(
+                &state,
+                account.as_deref(),
+                ip.as_deref(),
+                AppError::Unauthorized,
+            )
+            .await);
+        }
+        DomainResult::Err(TwoFactorError::InvalidCode) => {
+            return Err(record_login_failure(
+                &state,
+                account.as_deref(),
+                ip.as_deref(),
+                TwoFactorError::InvalidCode.into(),
+            )
+            .await);
+        }
+        DomainResult::Err(e) => return Err(e.into()),
+    };
+
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    let (jar, response) =
+        complete_login(&state, &user, req.session, user_agent, ip.as_deref()).await?;
+
+    Ok((jar, ApiResponse::ok(response)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "",
+    responses(
+        (status = 200, description = "Remove a user's authenticator and recovery codes"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Missing users:2fa:reset permission"),
+        (status = 404, description = "User not found")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn reset_user_two_factor(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<ApiResponse<()>, AppError> {
+    match usecases::reset(&state.db, id).await {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
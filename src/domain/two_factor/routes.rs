@@ -0,0 +1,23 @@
+use axum::Router;
+use axum::routing::{delete, post};
+
+use super::handlers;
+use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::RequirePermission;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/enroll", post(handlers::enroll))
+        .route("/confirm", post(handlers::confirm))
+        .route("/verify", post(handlers::verify))
+}
+
+pub fn admin_router(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", delete(handlers::reset_user_two_factor))
+        .route_layer(RequirePermission::new(
+            state,
+            Permission::UsersResetTwoFactor,
+        ))
+}
@@ -0,0 +1,560 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand_core::{OsRng, RngCore};
+use sqlx::{PgConnection, PgPool};
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use super::entities::challenge::TwoFactorChallengeEntity;
+use super::entities::user_totp::UserTotpEntity;
+use super::error::TwoFactorError;
+use crate::domain::users::entities::user_entity::UserEntity;
+use crate::shared::db_error::DbError;
+use crate::shared::security::token::{constant_time_eq, generate_token, hash_token};
+use crate::shared::types::result::DomainResult;
+
+const STEP_SECS: u64 = 30;
+const DIGITS: usize = 6;
+/// Steps accepted either side of the current one, for clock drift
+const SKEW: u64 = 1;
+const RECOVERY_CODE_COUNT: usize = 10;
+/// Wrong codes allowed per challenge before the password must be re-entered
+const MAX_CHALLENGE_ATTEMPTS: i32 = 5;
+
+/// =========================
+/// IS ENABLED
+/// =========================
+pub async fn is_enabled(pool: &PgPool, user_id: Uuid) -> DomainResult<bool, TwoFactorError> {
+    let enabled = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM user_totp
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL
+        )
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await;
+
+    match enabled {
+        Ok(e) => DomainResult::Ok(e),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// IS MISSING REQUIRED
+/// =========================
+/// True when the user holds one of `roles` but has no confirmed authenticator
+pub async fn is_missing_required(
+    pool: &PgPool,
+    user_id: Uuid,
+    roles: &[String],
+) -> DomainResult<bool, DbError> {
+    let missing = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM user_roles
+            WHERE user_id = $1 AND role = ANY($2)
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM user_totp
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(roles)
+    .fetch_one(pool)
+    .await;
+
+    match missing {
+        Ok(m) => DomainResult::Ok(m),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// ENROLL
+/// =========================
+/// Generates a fresh secret, replacing any unconfirmed one. Returns the
+/// base32 secret and its `otpauth://` URI.
+pub async fn enroll(
+    pool: &PgPool,
+    user_id: Uuid,
+    email: &str,
+    issuer: &str,
+) -> DomainResult<(String, String), TwoFactorError> {
+    // 1️⃣ build the authenticator entry
+    let secret = match Secret::generate_secret().to_bytes() {
+        Ok(bytes) => bytes,
+        Err(e) => return DomainResult::Err(TwoFactorError::Totp(format!("{:?}", e))),
+    };
+
+    let totp = match TOTP::new(
+        Algorithm::SHA1,
+        DIGITS,
+        SKEW as u8,
+        STEP_SECS,
+        secret,
+        Some(issuer.to_string()),
+        email.to_string(),
+    ) {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(TwoFactorError::Totp(e.to_string())),
+    };
+
+    let encoded = totp.get_secret_base32();
+
+    // 2️⃣ store it as pending, never overwriting a confirmed one
+    let res = sqlx::query(
+        r#"
+        INSERT INTO user_totp (user_id, secret)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id) DO UPDATE
+        SET secret = EXCLUDED.secret,
+            last_used_step = NULL,
+            created_at = CURRENT_TIMESTAMP
+        WHERE user_totp.confirmed_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .bind(&encoded)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::Err(TwoFactorError::AlreadyEnabled),
+        Ok(_) => DomainResult::Ok((encoded, totp.get_url())),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// CONFIRM ENROLLMENT
+/// =========================
+/// Activates the pending secret once the user proves they can produce
+/// codes, and returns freshly generated recovery codes (shown once).
+pub async fn confirm_enrollment(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: &str,
+) -> DomainResult<Vec<String>, TwoFactorError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ lock the pending secret
+    let totp = match lock_totp(&mut tx, user_id).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return DomainResult::Err(TwoFactorError::NotEnrolled),
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if totp.confirmed_at.is_some() {
+        return DomainResult::Err(TwoFactorError::AlreadyEnabled);
+    }
+
+    // 2️⃣ check the code
+    let step = match matching_step(&totp, code) {
+        Ok(Some(step)) => step,
+        Ok(None) => return DomainResult::Err(TwoFactorError::InvalidCode),
+        Err(e) => return DomainResult::Err(e),
+    };
+
+    // 3️⃣ activate
+    let res = sqlx::query(
+        r#"
+        UPDATE user_totp
+        SET confirmed_at = CURRENT_TIMESTAMP, last_used_step = $2
+        WHERE user_id = $1
+        "#,
+    )
+    .bind(user_id)
+    .bind(step)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    // 4️⃣ issue recovery codes
+    let codes = match replace_recovery_codes(&mut tx, user_id).await {
+        Ok(c) => c,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    DomainResult::Ok(codes)
+}
+
+/// =========================
+/// CREATE CHALLENGE
+/// =========================
+/// Partial-auth token handed out after a correct password
+pub async fn create_challenge(
+    pool: &PgPool,
+    user_id: Uuid,
+    ttl_secs: i64,
+) -> DomainResult<String, TwoFactorError> {
+    let token = generate_token();
+
+    let res = sqlx::query(
+        r#"
+        INSERT INTO two_factor_challenges (user_id, token_hash, expires_at)
+        VALUES ($1, $2, CURRENT_TIMESTAMP + make_interval(secs => $3))
+        "#,
+    )
+    .bind(user_id)
+    .bind(hash_token(&token))
+    .bind(ttl_secs as f64)
+    .execute(pool)
+    .await;
+
+    match res {
+        Ok(_) => DomainResult::Ok(token),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// CHALLENGE EMAIL
+/// =========================
+/// Email of the user a challenge was issued to, so failures can be
+/// counted against the account. Expired or used challenges still resolve.
+pub async fn challenge_email(
+    pool: &PgPool,
+    challenge_token: &str,
+) -> DomainResult<String, TwoFactorError> {
+    let email = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT u.email
+        FROM two_factor_challenges c
+        JOIN users u ON u.id = c.user_id
+        WHERE c.token_hash = $1
+        "#,
+    )
+    .bind(hash_token(challenge_token))
+    .fetch_optional(pool)
+    .await;
+
+    match email {
+        Ok(Some(e)) => DomainResult::Ok(e),
+        Ok(None) => DomainResult::NotFound,
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// VERIFY CHALLENGE
+/// =========================
+/// Completes a login with either a TOTP code or a recovery code.
+/// Unknown, expired, consumed or exhausted challenges yield `NotFound`;
+/// a wrong code still counts against the challenge.
+pub async fn verify_challenge(
+    pool: &PgPool,
+    challenge_token: &str,
+    code: Option<&str>,
+    recovery_code: Option<&str>,
+) -> DomainResult<UserEntity, TwoFactorError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 1️⃣ lock the challenge
+    let challenge = match sqlx::query_as::<_, TwoFactorChallengeEntity>(
+        r#"
+        SELECT id, user_id, expires_at, attempts, consumed_at
+        FROM two_factor_challenges
+        WHERE token_hash = $1
+          AND consumed_at IS NULL
+          AND expires_at > CURRENT_TIMESTAMP
+          AND attempts < $2
+        FOR UPDATE
+        "#,
+    )
+    .bind(hash_token(challenge_token))
+    .bind(MAX_CHALLENGE_ATTEMPTS)
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(c)) => c,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 2️⃣ count the attempt before checking it
+    let res = sqlx::query("UPDATE two_factor_challenges SET attempts = attempts + 1 WHERE id = $1")
+        .bind(challenge.id)
+        .execute(&mut *tx)
+        .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    // 3️⃣ check the second factor
+    let accepted = match (code, recovery_code) {
+        (Some(code), _) => accept_totp(&mut tx, challenge.user_id, code).await,
+        (None, Some(recovery_code)) => {
+            redeem_recovery_code(&mut tx, challenge.user_id, recovery_code)
+                .await
+                .map_err(TwoFactorError::from)
+        }
+        (None, None) => Ok(false),
+    };
+
+    match accepted {
+        Ok(true) => {}
+        Ok(false) => {
+            // keep the attempt count
+            if let Err(e) = tx.commit().await {
+                return DomainResult::Err(e.into());
+            }
+            return DomainResult::Err(TwoFactorError::InvalidCode);
+        }
+        Err(e) => return DomainResult::Err(e),
+    }
+
+    // 4️⃣ consume the challenge
+    let res = sqlx::query(
+        "UPDATE two_factor_challenges SET consumed_at = CURRENT_TIMESTAMP WHERE id = $1",
+    )
+    .bind(challenge.id)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    let user = match sqlx::query_as::<_, UserEntity>(
+        r#"
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
+        FROM users
+        WHERE id = $1 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(challenge.user_id)
+    .fetch_optional(&mut *tx)
+    .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    DomainResult::Ok(user)
+}
+
+/// =========================
+/// RESET (admin)
+/// =========================
+/// Removes the authenticator, recovery codes and open challenges so the
+/// user can enroll again. Unknown users yield `NotFound`.
+pub async fn reset(pool: &PgPool, user_id: Uuid) -> DomainResult<(), TwoFactorError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users WHERE id = $1)")
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => return DomainResult::NotFound,
+        Err(e) => return DomainResult::Err(e.into()),
+    }
+
+    for statement in [
+        "DELETE FROM user_totp WHERE user_id = $1",
+        "DELETE FROM user_recovery_codes WHERE user_id = $1",
+        "DELETE FROM two_factor_challenges WHERE user_id = $1",
+    ] {
+        if let Err(e) = sqlx::query(statement).bind(user_id).execute(&mut *tx).await {
+            return DomainResult::Err(e.into());
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    DomainResult::Ok(())
+}
+
+/// =========================
+/// HELPERS
+/// =========================
+async fn lock_totp(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<Option<UserTotpEntity>, sqlx::Error> {
+    sqlx::query_as::<_, UserTotpEntity>(
+        r#"
+        SELECT user_id, secret, confirmed_at, last_used_step
+        FROM user_totp
+        WHERE user_id = $1
+        FOR UPDATE
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(conn)
+    .await
+}
+
+/// Accepts a code from a confirmed authenticator, at most once per time step
+async fn accept_totp(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    code: &str,
+) -> Result<bool, TwoFactorError> {
+    let totp = match lock_totp(conn, user_id).await? {
+        Some(t) if t.confirmed_at.is_some() => t,
+        _ => return Ok(false),
+    };
+
+    let Some(step) = matching_step(&totp, code)? else {
+        return Ok(false);
+    };
+
+    sqlx::query("UPDATE user_totp SET last_used_step = $2 WHERE user_id = $1")
+        .bind(user_id)
+        .bind(step)
+        .execute(conn)
+        .await?;
+
+    Ok(true)
+}
+
+/// Time step `code` was generated for, skipping steps already used
+fn matching_step(totp: &UserTotpEntity, code: &str) -> Result<Option<i64>, TwoFactorError> {
+    let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+
+    let secret = Secret::Encoded(totp.secret.clone())
+        .to_bytes()
+        .map_err(|e| TwoFactorError::Totp(format!("{:?}", e)))?;
+
+    // issuer and account only matter for the URI
+    let generator = TOTP::new_unchecked(
+        Algorithm::SHA1,
+        DIGITS,
+        SKEW as u8,
+        STEP_SECS,
+        secret,
+        None,
+        String::new(),
+    );
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let current = now / STEP_SECS;
+
+    // check every candidate so timing doesn't reveal which one matched
+    let mut matched = None;
+    for step in current.saturating_sub(SKEW)..=current + SKEW {
+        let expected = generator.generate(step * STEP_SECS);
+        let fresh = totp.last_used_step.is_none_or(|last| step as i64 > last);
+        if constant_time_eq(expected.as_bytes(), code.as_bytes()) && fresh {
+            matched = Some(step as i64);
+        }
+    }
+
+    Ok(matched)
+}
+
+async fn redeem_recovery_code(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    code: &str,
+) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query(
+        r#"
+        UPDATE user_recovery_codes
+        SET used_at = CURRENT_TIMESTAMP
+        WHERE id = (
+            SELECT id FROM user_recovery_codes
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            LIMIT 1
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(hash_token(&normalize_recovery_code(code)))
+    .execute(conn)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+async fn replace_recovery_codes(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| generate_recovery_code())
+        .collect();
+    let hashes: Vec<String> = codes
+        .iter()
+        .map(|code| hash_token(&normalize_recovery_code(code)))
+        .collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO user_recovery_codes (user_id, code_hash)
+        SELECT $1, UNNEST($2::VARCHAR[])
+        "#,
+    )
+    .bind(user_id)
+    .bind(&hashes)
+    .execute(conn)
+    .await?;
+
+    Ok(codes)
+}
+
+/// `xxxxx-xxxxx` from an alphabet without look-alike characters
+fn generate_recovery_code() -> String {
+    const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+    // rejection sampling keeps every character equally likely
+    let limit = 256 - 256 % ALPHABET.len();
+    let mut chars = String::with_capacity(10);
+    while chars.len() < 10 {
+        let mut byte = [0u8; 1];
+        OsRng.fill_bytes(&mut byte);
+        if (byte[0] as usize) < limit {
+            chars.push(ALPHABET[byte[0] as usize % ALPHABET.len()] as char);
+        }
+    }
+
+    format!("{}-{}", &chars[..5], &chars[5..])
+}
+
+/// Case, dashes and spaces don't matter when typing a recovery code
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
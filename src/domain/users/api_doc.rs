@@ -1,8 +1,11 @@
 use utoipa::OpenApi;
 
 use super::dtos::create::CreateUserRequest;
+use super::dtos::me::{ChangePasswordRequest, UpdateMeRequest};
+use super::dtos::patch::{NamePatch, PatchUserRequest, PersonNamePatch};
 use super::dtos::response::UserResponse;
 use super::dtos::update::UpdateUserRequest;
+use crate::shared::pagination::PaginationMeta;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -11,8 +14,24 @@ use super::dtos::update::UpdateUserRequest;
         super::handlers::create_user,
         super::handlers::find_one_user,
         super::handlers::update_user,
-        super::handlers::delete_user
+        super::handlers::patch_user,
+        super::handlers::delete_user,
+        super::handlers::restore_user,
+        super::handlers::get_me,
+        super::handlers::update_me,
+        super::handlers::change_my_password,
+        super::handlers::delete_me
     ),
-    components(schemas(UserResponse, CreateUserRequest, UpdateUserRequest))
+    components(schemas(
+        UserResponse,
+        CreateUserRequest,
+        UpdateUserRequest,
+        PatchUserRequest,
+        NamePatch,
+        PersonNamePatch,
+        UpdateMeRequest,
+        ChangePasswordRequest,
+        PaginationMeta
+    ))
 )]
 pub struct UsersApi;
@@ -1,10 +1,27 @@
+use crate::shared::validation::{Validate, Validator};
 use crate::{domain::users::entities::people_name::PersonName, shared::types::hash::Hash};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// `user_names.lang` is `VARCHAR(5)`
+pub const MAX_NAME_LOCALE_LEN: usize = 5;
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
     pub name: Hash<String, PersonName>,
     pub email: String,
     pub password: String,
 }
+
+impl Validate for CreateUserRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", &self.email)
+            .required("password", &self.password)
+            .locales(
+                "name.values",
+                &self.name.values,
+                MAX_NAME_LOCALE_LEN,
+                |v, path, name| name.validate(v, path),
+            );
+    }
+}
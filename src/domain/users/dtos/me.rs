@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::patch::{NamePatch, PatchUserRequest, validate_members};
+use crate::shared::types::patch::Patch;
+use crate::shared::validation::{Validate, Validator};
+
+/// Merge patch of the profile fields a user may change on their own account.
+///
+/// Unknown fields are rejected, so roles, verification state and the
+/// password (see `/users/me/password`) can't be smuggled in.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateMeRequest {
+    #[serde(default)]
+    #[schema(value_type = Option<NamePatch>)]
+    pub name: Patch<NamePatch>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub email: Patch<String>,
+}
+
+impl Validate for UpdateMeRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_members(v, &self.name, &self.email);
+    }
+}
+
+impl From<UpdateMeRequest> for PatchUserRequest {
+    fn from(req: UpdateMeRequest) -> Self {
+        Self {
+            name: req.name,
+            email: req.email,
+            password: Patch::Absent,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+impl Validate for ChangePasswordRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.required("current_password", &self.current_password)
+            .required("new_password", &self.new_password);
+    }
+}
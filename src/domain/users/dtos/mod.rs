@@ -1,3 +1,5 @@
 pub mod create;
+pub mod me;
+pub mod patch;
 pub mod response;
 pub mod update;
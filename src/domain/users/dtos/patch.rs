@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::domain::users::entities::people_name::PersonName;
+use crate::shared::types::patch::Patch;
+use crate::shared::validation::{Validate, Validator};
+
+/// JSON Merge Patch (RFC 7396) of a user: members left out are kept.
+///
+/// Within `name.values` each locale can be added, changed field by field,
+/// or removed with `"th": null`. `name`, `email` and `password` themselves
+/// can't be removed.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PatchUserRequest {
+    #[serde(default)]
+    #[schema(value_type = Option<NamePatch>)]
+    pub name: Patch<NamePatch>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub email: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub password: Patch<String>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NamePatch {
+    /// `null` removes the locale
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, PersonNamePatch>)]
+    pub values: HashMap<String, Option<PersonNamePatch>>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PersonNamePatch {
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub first: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub middle: Patch<String>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub last: Patch<String>,
+}
+
+impl PersonNamePatch {
+    /// Merge onto `current`, or onto an empty name for a new locale.
+    /// A removed part becomes empty, which validation rejects where required.
+    pub fn apply(self, current: Option<PersonName>) -> PersonName {
+        let current = current.unwrap_or(PersonName {
+            first: String::new(),
+            middle: String::new(),
+            last: String::new(),
+        });
+
+        let merge = |patch: Patch<String>, current: String| match patch {
+            Patch::Absent => current,
+            Patch::Null => String::new(),
+            Patch::Value(value) => value,
+        };
+
+        PersonName {
+            first: merge(self.first, current.first),
+            middle: merge(self.middle, current.middle),
+            last: merge(self.last, current.last),
+        }
+    }
+}
+
+/// Null members that can't be removed; the merged names are validated
+/// once the current ones are known
+pub(crate) fn validate_members(v: &mut Validator, name: &Patch<NamePatch>, email: &Patch<String>) {
+    if matches!(name, Patch::Null) {
+        v.add("name", "required", "must not be null");
+    }
+    match email {
+        Patch::Value(email) => {
+            v.email("email", email);
+        }
+        Patch::Null => {
+            v.add("email", "required", "must not be null");
+        }
+        Patch::Absent => {}
+    }
+}
+
+impl Validate for PatchUserRequest {
+    fn validate(&self, v: &mut Validator) {
+        validate_members(v, &self.name, &self.email);
+
+        match &self.password {
+            Patch::Value(password) => {
+                v.required("password", password);
+            }
+            Patch::Null => {
+                v.add("password", "required", "must not be null");
+            }
+            Patch::Absent => {}
+        }
+    }
+}
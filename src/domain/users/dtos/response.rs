@@ -5,15 +5,25 @@ use uuid::Uuid;
 
 use crate::domain::users::entities::User;
 use crate::domain::users::entities::people_name::PersonName;
+use crate::shared::types::etag::etag;
 use crate::shared::types::hash::Hash;
+use crate::shared::types::locale::Locale;
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: Uuid,
     pub name: Hash<String, PersonName>,
     pub email: String,
+    pub verified_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Set only on soft-deleted users, returned with `include_deleted`
+    pub deleted_at: Option<NaiveDateTime>,
+    /// Send back in `If-Match` to update only this version; also names
+    /// the locales of `name`, which change with `lang`
+    pub etag: String,
+    #[serde(skip)]
+    version: i64,
 }
 
 impl From<User> for UserResponse {
@@ -22,8 +32,29 @@ impl From<User> for UserResponse {
             id: user.id,
             name: user.name,
             email: user.email,
+            verified_at: user.verified_at,
             created_at: user.created_at,
             updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+            etag: String::new(),
+            version: user.version,
         }
+        .tagged()
+    }
+}
+
+impl UserResponse {
+    /// Narrow `name` to the best match for the requested locale
+    pub fn localized(mut self, locale: &Locale) -> Self {
+        self.name = self.name.localize(locale);
+        self.tagged()
+    }
+
+    /// Tag the version together with the locales `name` carries
+    fn tagged(mut self) -> Self {
+        let mut locales: Vec<&str> = self.name.values.keys().map(String::as_str).collect();
+        locales.sort_unstable();
+        self.etag = etag(self.version, &locales.join("."));
+        self
     }
 }
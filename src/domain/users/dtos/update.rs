@@ -1,10 +1,28 @@
+use super::create::MAX_NAME_LOCALE_LEN;
+use crate::shared::validation::{Validate, Validator};
 use crate::{domain::users::entities::people_name::PersonName, shared::types::hash::Hash};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+/// Full replacement for `PUT`: every locale not listed is removed.
+/// `password` is write-only and kept when left out.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateUserRequest {
-    pub name: Option<Hash<String, PersonName>>,
-    pub email: Option<String>,
+    pub name: Hash<String, PersonName>,
+    pub email: String,
     pub password: Option<String>,
 }
+
+impl Validate for UpdateUserRequest {
+    fn validate(&self, v: &mut Validator) {
+        v.email("email", &self.email).locales(
+            "name.values",
+            &self.name.values,
+            MAX_NAME_LOCALE_LEN,
+            |v, path, name| name.validate(v, path),
+        );
+        if let Some(password) = &self.password {
+            v.required("password", password);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use super::people_name::PersonName;
+
 #[derive(Debug, FromRow)]
 pub struct UserNameEntity {
     pub user_id: Uuid,
@@ -9,3 +11,13 @@ pub struct UserNameEntity {
     pub middle_name: String,
     pub last_name: String,
 }
+
+impl From<UserNameEntity> for PersonName {
+    fn from(n: UserNameEntity) -> Self {
+        PersonName {
+            first: n.first_name,
+            middle: n.middle_name,
+            last: n.last_name,
+        }
+    }
+}
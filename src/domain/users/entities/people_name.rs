@@ -1,9 +1,26 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::shared::validation::Validator;
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PersonName {
     pub first: String,
     pub middle: String,
     pub last: String,
 }
+
+impl PersonName {
+    pub fn parts(&self) -> [&str; 3] {
+        [&self.first, &self.middle, &self.last]
+    }
+
+    /// Rules matching the `user_names` columns
+    pub fn validate(&self, v: &mut Validator, path: &str) {
+        v.required(&format!("{}.first", path), &self.first)
+            .length(&format!("{}.first", path), &self.first, 0, 255)
+            .length(&format!("{}.middle", path), &self.middle, 0, 255)
+            .required(&format!("{}.last", path), &self.last)
+            .length(&format!("{}.last", path), &self.last, 0, 255);
+    }
+}
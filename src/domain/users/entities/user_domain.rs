@@ -8,6 +8,12 @@ pub struct User {
     pub name: Hash<String, PersonName>,
     pub email: String,
     pub password: String,
+    /// `None` until the current email address is confirmed
+    pub verified_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// Set while soft-deleted, until restored or purged
+    pub deleted_at: Option<NaiveDateTime>,
+    /// Bumped on every change, served as the `ETag`
+    pub version: i64,
 }
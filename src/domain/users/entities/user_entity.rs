@@ -7,6 +7,9 @@ pub struct UserEntity {
     pub id: Uuid,
     pub email: String,
     pub password: String,
+    pub verified_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub version: i64,
 }
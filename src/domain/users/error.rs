@@ -0,0 +1,72 @@
+use std::fmt;
+
+use axum::http::StatusCode;
+
+use crate::shared::db_error::DbError;
+use crate::shared::error::{AppError, FieldError};
+
+/// Failures of the users usecases (see `DbError` for why this isn't `std::error::Error`)
+#[derive(Debug)]
+pub enum UserError {
+    /// `users.email` is already taken
+    EmailTaken,
+    /// Merged request rejected by the field rules
+    Invalid(Vec<FieldError>),
+    /// Password rejected by the configured policy
+    WeakPassword(Vec<FieldError>),
+    /// `current_password` didn't match on a self-service change
+    WrongPassword,
+    /// `If-Match` names a version other than the current one
+    PreconditionFailed,
+    /// Password hashing failed
+    Hashing(String),
+    Db(DbError),
+}
+
+impl From<sqlx::Error> for UserError {
+    fn from(err: sqlx::Error) -> Self {
+        match DbError::from(err) {
+            db if db.is_unique_violation("users_email_key") => UserError::EmailTaken,
+            // every column is length-checked by the DTOs; Postgres doesn't say
+            // which value overflowed, so anything else stays a generic 400
+            db => UserError::Db(db),
+        }
+    }
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserError::EmailTaken => write!(f, "email already in use"),
+            UserError::Invalid(_) => write!(f, "request validation failed"),
+            UserError::WeakPassword(_) => write!(f, "password rejected by policy"),
+            UserError::WrongPassword => write!(f, "current password is incorrect"),
+            UserError::PreconditionFailed => write!(f, "user was modified since it was read"),
+            UserError::Hashing(msg) => write!(f, "password hashing failed: {}", msg),
+            UserError::Db(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<UserError> for AppError {
+    fn from(err: UserError) -> Self {
+        match err {
+            UserError::EmailTaken => AppError::conflict("Email already in use"),
+            UserError::Invalid(errors) | UserError::WeakPassword(errors) => {
+                AppError::Validation(errors)
+            }
+            UserError::WrongPassword => AppError::Validation(vec![FieldError::new(
+                "current_password",
+                "incorrect",
+                "current password is incorrect",
+            )]),
+            UserError::PreconditionFailed => AppError::problem(
+                StatusCode::PRECONDITION_FAILED,
+                "precondition_failed",
+                "User was modified since it was read, fetch it again",
+            ),
+            UserError::Hashing(msg) => AppError::internal_server_error(msg),
+            UserError::Db(db) => db.into(),
+        }
+    }
+}
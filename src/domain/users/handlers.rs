@@ -1,31 +1,74 @@
-use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::State;
+use axum::http::HeaderValue;
+use axum::http::header::VARY;
+use axum::response::Response;
+use axum_extra::extract::cookie::CookieJar;
 use uuid::Uuid;
 
 use super::dtos::create::CreateUserRequest;
+use super::dtos::me::{ChangePasswordRequest, UpdateMeRequest};
+use super::dtos::patch::PatchUserRequest;
 use super::dtos::response::UserResponse;
 use super::dtos::update::UpdateUserRequest;
+use super::error::UserError;
+use super::query::{DeleteUserQuery, FindUserQuery, ListUsersQuery};
 use super::usecases;
 use crate::app::state::AppState;
+use crate::domain::auth::extractors::AuthUser;
+use crate::domain::auth::handlers::{
+    check_login_throttle, record_login_failure, spawn_email_verification,
+};
+use crate::domain::lockout;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::require_permission;
+use crate::domain::sessions::cookies;
 use crate::shared::error::AppError;
-use crate::shared::response::ApiResponse;
+use crate::shared::extractors::{
+    AcceptLocale, ClientIp, IfMatch, IfNoneMatch, ValidatedJson, ValidatedPath, ValidatedQuery,
+};
+use crate::shared::pagination::{PaginationMeta, clamp_per_page};
+use crate::shared::response::{ApiResponse, not_modified};
 use crate::shared::types::result::DomainResult;
 
 #[utoipa::path(
     get,
     path = "",
+    params(
+        ListUsersQuery,
+        ("lang" = Option<String>, Query, description = "Preferred locales, e.g. `th,en`; `*` returns every translation"),
+        ("Accept-Language" = Option<String>, Header, description = "Used when `lang` is absent")
+    ),
     responses(
-        (status = 200, description = "Get all users", body = [UserResponse])
+        (status = 200, description = "Get a page of users", body = [UserResponse]),
+        (status = 400, description = "Invalid cursor"),
+        (status = 403, description = "Missing users:read permission, or users:delete for include_deleted")
     )
 )]
 pub async fn get_all_users(
     State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedQuery(query): ValidatedQuery<ListUsersQuery>,
+    AcceptLocale(locale): AcceptLocale,
 ) -> Result<ApiResponse<Vec<UserResponse>>, AppError> {
-    match usecases::get_all_users(&state.db).await {
-        DomainResult::Ok(users) => Ok(ApiResponse::ok(
-            users.into_iter().map(UserResponse::from).collect(),
-        )),
-        DomainResult::Err(e) => Err(AppError::internal_server_error(e)),
+    if query.include_deleted {
+        require_deleted_access(&state, &user).await?;
+    }
+
+    let cursor = query.decode_cursor().map_err(AppError::bad_request)?;
+    let page_number = cursor.is_none().then(|| query.page.unwrap_or(1).max(1));
+
+    match usecases::get_all_users(&state.db, &query, cursor).await {
+        DomainResult::Ok(page) => {
+            let page = page.map(|user| UserResponse::from(user).localized(&locale));
+            let meta = PaginationMeta {
+                total: page.total,
+                page: page_number,
+                per_page: clamp_per_page(query.per_page),
+                next_cursor: page.next_cursor,
+            };
+            Ok(ApiResponse::paginated(page.items, meta))
+        }
+        DomainResult::Err(e) => Err(e.into()),
         _ => Err(AppError::internal_server_error(
             "Unexpected error".to_string(),
         )),
@@ -36,21 +79,48 @@ pub async fn get_all_users(
     get,
     path = "/{id}",
     responses(
-        (status = 200, description = "Get user by ID", body = UserResponse),
-        (status = 404, description = "User not found")
+        (status = 200, description = "Get user by ID, with its version in `ETag`", body = UserResponse),
+        (status = 304, description = "`If-None-Match` matches the current version"),
+        (status = 403, description = "Missing users:read permission, or users:delete for include_deleted"),
+        (status = 404, description = "User not found, or soft-deleted")
     ),
     params(
-        ("id" = Uuid, Path, description = "User ID")
+        ("id" = Uuid, Path, description = "User ID"),
+        FindUserQuery,
+        ("lang" = Option<String>, Query, description = "Preferred locales, e.g. `th,en`; `*` returns every translation"),
+        ("Accept-Language" = Option<String>, Header, description = "Used when `lang` is absent"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from an earlier read, answered with 304 while unchanged")
     )
 )]
 pub async fn find_one_user(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-) -> Result<ApiResponse<UserResponse>, AppError> {
-    match usecases::find_one_user(&state.db, id).await {
-        DomainResult::Ok(user) => Ok(ApiResponse::ok(user.into())),
+    user: AuthUser,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<FindUserQuery>,
+    AcceptLocale(locale): AcceptLocale,
+    IfNoneMatch(if_none_match): IfNoneMatch,
+) -> Result<Response, AppError> {
+    if query.include_deleted {
+        require_deleted_access(&state, &user).await?;
+    }
+
+    match usecases::find_one_user(&state.db, id, query.include_deleted).await {
+        DomainResult::Ok(user) => {
+            let response = UserResponse::from(user).localized(&locale);
+            let etag = response.etag.clone();
+
+            // the tag differs per locale, so caches must key on it too
+            let mut response = match if_none_match {
+                Some(tags) if tags.matches(&etag) => not_modified(&etag),
+                _ => ApiResponse::ok(response).with_etag(&etag),
+            };
+            response
+                .headers_mut()
+                .insert(VARY, HeaderValue::from_static("accept-language"));
+            Ok(response)
+        }
         DomainResult::NotFound => Err(AppError::not_found("User not found")),
-        DomainResult::Err(e) => Err(AppError::internal_server_error(e)),
+        DomainResult::Err(e) => Err(e.into()),
     }
 }
 
@@ -59,16 +129,28 @@ pub async fn find_one_user(
     path = "",
     request_body = CreateUserRequest,
     responses(
-        (status = 201, description = "Create new user", body = UserResponse)
+        (status = 201, description = "Create new user", body = UserResponse),
+        (status = 409, description = "Email already in use"),
+        (status = 422, description = "Request body failed validation")
     )
 )]
 pub async fn create_user(
     State(state): State<AppState>,
-    Json(req): Json<CreateUserRequest>,
+    ValidatedJson(req): ValidatedJson<CreateUserRequest>,
 ) -> Result<ApiResponse<UserResponse>, AppError> {
-    match usecases::create_user(&state.db, req).await {
-        DomainResult::Ok(user) => Ok(ApiResponse::created(user.into())),
-        DomainResult::Err(e) => Err(AppError::internal_server_error(e)),
+    match usecases::create_user(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        req,
+    )
+    .await
+    {
+        DomainResult::Ok(user) => {
+            spawn_email_verification(state.clone(), user.id, user.email.clone());
+            Ok(ApiResponse::created(user.into()))
+        }
+        DomainResult::Err(e) => Err(e.into()),
         _ => Err(AppError::internal_server_error(
             "Unexpected error".to_string(),
         )),
@@ -80,22 +162,91 @@ pub async fn create_user(
     path = "/{id}",
     request_body = UpdateUserRequest,
     responses(
-        (status = 200, description = "Update user", body = UserResponse),
-        (status = 404, description = "User not found")
+        (status = 200, description = "Replace user, removing name locales left out", body = UserResponse),
+        (status = 403, description = "Missing users:write permission"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Email already in use"),
+        (status = 412, description = "`If-Match` doesn't match the current version"),
+        (status = 422, description = "Request body failed validation")
     ),
     params(
-        ("id" = Uuid, Path, description = "User ID")
+        ("id" = Uuid, Path, description = "User ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag from an earlier read; the change is refused with 412 once the user moved on")
     )
 )]
 pub async fn update_user(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
-    Json(req): Json<UpdateUserRequest>,
-) -> Result<ApiResponse<UserResponse>, AppError> {
-    match usecases::update_user(&state.db, id, req).await {
-        DomainResult::Ok(user) => Ok(ApiResponse::ok(user.into())),
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    IfMatch(if_match): IfMatch,
+    ValidatedJson(req): ValidatedJson<UpdateUserRequest>,
+) -> Result<Response, AppError> {
+    match usecases::update_user(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        id,
+        req,
+        if_match.as_ref(),
+    )
+    .await
+    {
+        DomainResult::Ok((user, email_changed)) => {
+            if email_changed {
+                spawn_email_verification(state.clone(), user.id, user.email.clone());
+            }
+            let response = UserResponse::from(user);
+            let etag = response.etag.clone();
+            Ok(ApiResponse::ok(response).with_etag(&etag))
+        }
         DomainResult::NotFound => Err(AppError::not_found("User not found")),
-        DomainResult::Err(e) => Err(AppError::internal_server_error(e)),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}",
+    request_body(content = PatchUserRequest, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Merge-patch user (RFC 7396); `null` removes a name locale", body = UserResponse),
+        (status = 403, description = "Missing users:write permission"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Email already in use"),
+        (status = 412, description = "`If-Match` doesn't match the current version"),
+        (status = 422, description = "Patch or merged result failed validation")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag from an earlier read; the change is refused with 412 once the user moved on")
+    )
+)]
+pub async fn patch_user(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    IfMatch(if_match): IfMatch,
+    ValidatedJson(req): ValidatedJson<PatchUserRequest>,
+) -> Result<Response, AppError> {
+    match usecases::patch_user(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        &state.config.app.required_locales,
+        id,
+        req,
+        if_match.as_ref(),
+    )
+    .await
+    {
+        DomainResult::Ok((user, email_changed)) => {
+            if email_changed {
+                spawn_email_verification(state.clone(), user.id, user.email.clone());
+            }
+            let response = UserResponse::from(user);
+            let etag = response.etag.clone();
+            Ok(ApiResponse::ok(response).with_etag(&etag))
+        }
+        DomainResult::NotFound => Err(AppError::not_found("User not found")),
+        DomainResult::Err(e) => Err(e.into()),
     }
 }
 
@@ -103,20 +254,211 @@ pub async fn update_user(
     delete,
     path = "/{id}",
     responses(
-        (status = 200, description = "Delete user"),
-        (status = 404, description = "User not found")
+        (status = 200, description = "Soft-delete user, or delete permanently with `permanent=true`"),
+        (status = 403, description = "Missing users:delete permission"),
+        (status = 404, description = "User not found, or already soft-deleted"),
+        (status = 412, description = "`If-Match` doesn't match the current version")
     ),
     params(
-        ("id" = Uuid, Path, description = "User ID")
+        ("id" = Uuid, Path, description = "User ID"),
+        DeleteUserQuery,
+        ("If-Match" = Option<String>, Header, description = "ETag from an earlier read; the change is refused with 412 once the user moved on")
     )
 )]
 pub async fn delete_user(
     State(state): State<AppState>,
-    Path(id): Path<Uuid>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<DeleteUserQuery>,
+    IfMatch(if_match): IfMatch,
 ) -> Result<ApiResponse<()>, AppError> {
-    match usecases::delete_user(&state.db, id).await {
-        DomainResult::Ok(_) => Ok(ApiResponse::ok(())),
+    let result = if query.permanent {
+        usecases::purge_user(&state.db, id, if_match.as_ref()).await
+    } else {
+        usecases::delete_user(&state.db, id, if_match.as_ref()).await
+    };
+
+    match result {
+        DomainResult::Ok(_) => Ok(ApiResponse::ok_empty()),
         DomainResult::NotFound => Err(AppError::not_found("User not found")),
-        DomainResult::Err(e) => Err(AppError::internal_server_error(e)),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/restore",
+    responses(
+        (status = 200, description = "Restore a soft-deleted user", body = UserResponse),
+        (status = 403, description = "Missing users:delete permission"),
+        (status = 404, description = "No soft-deleted user with this ID")
+    ),
+    params(
+        ("id" = Uuid, Path, description = "User ID")
+    )
+)]
+pub async fn restore_user(
+    State(state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<Response, AppError> {
+    match usecases::restore_user(&state.db, id).await {
+        DomainResult::Ok(user) => {
+            let response = UserResponse::from(user);
+            let etag = response.etag.clone();
+            Ok(ApiResponse::ok(response).with_etag(&etag))
+        }
+        DomainResult::NotFound => Err(AppError::not_found("Deleted user not found")),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/me",
+    responses(
+        (status = 200, description = "Get the authenticated user", body = UserResponse),
+        (status = 401, description = "Missing or invalid credentials")
+    ),
+    params(
+        ("lang" = Option<String>, Query, description = "Preferred locales, e.g. `th,en`; `*` returns every translation"),
+        ("Accept-Language" = Option<String>, Header, description = "Used when `lang` is absent")
+    )
+)]
+pub async fn get_me(
+    State(state): State<AppState>,
+    user: AuthUser,
+    AcceptLocale(locale): AcceptLocale,
+) -> Result<ApiResponse<UserResponse>, AppError> {
+    match usecases::find_one_user(&state.db, user.id, false).await {
+        DomainResult::Ok(user) => Ok(ApiResponse::ok(UserResponse::from(user).localized(&locale))),
+        DomainResult::NotFound => Err(AppError::Unauthorized),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/me",
+    request_body(content = UpdateMeRequest, content_type = "application/merge-patch+json"),
+    responses(
+        (status = 200, description = "Merge-patch the authenticated user's name or email (RFC 7396)", body = UserResponse),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys"),
+        (status = 409, description = "Email already in use"),
+        (status = 422, description = "Request body failed validation, or names a field users can't change themselves")
+    )
+)]
+pub async fn update_me(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ValidatedJson(req): ValidatedJson<UpdateMeRequest>,
+) -> Result<ApiResponse<UserResponse>, AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::patch_user(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        &state.config.app.required_locales,
+        user.id,
+        req.into(),
+        None,
+    )
+    .await
+    {
+        DomainResult::Ok((user, email_changed)) => {
+            if email_changed {
+                spawn_email_verification(state.clone(), user.id, user.email.clone());
+            }
+            Ok(ApiResponse::ok(user.into()))
+        }
+        DomainResult::NotFound => Err(AppError::Unauthorized),
+        DomainResult::Err(e) => Err(e.into()),
     }
 }
+
+#[utoipa::path(
+    post,
+    path = "/me/password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Change password, signing out every other session and refresh token"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys"),
+        (status = 412, description = "User changed while the password was being checked, try again"),
+        (status = 422, description = "Current password incorrect, or new password failed validation"),
+        (status = 429, description = "Account or client locked after repeated failures, see Retry-After")
+    )
+)]
+pub async fn change_my_password(
+    State(state): State<AppState>,
+    user: AuthUser,
+    ClientIp(ip): ClientIp,
+    ValidatedJson(req): ValidatedJson<ChangePasswordRequest>,
+) -> Result<ApiResponse<()>, AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    // wrong guesses count like failed logins, so a stolen session can't
+    // brute-force the password
+    let account = lockout::usecases::account_key(&user.email);
+    check_login_throttle(&state, Some(&account), ip.as_deref()).await?;
+
+    match usecases::change_password(
+        &state.db,
+        &state.passwords,
+        &state.config.password_policy,
+        user.id,
+        &req.current_password,
+        &req.new_password,
+        user.session_id,
+    )
+    .await
+    {
+        DomainResult::Ok(()) => Ok(ApiResponse::ok_empty()),
+        DomainResult::NotFound => Err(AppError::Unauthorized),
+        DomainResult::Err(UserError::WrongPassword) => Err(record_login_failure(
+            &state,
+            Some(&account),
+            ip.as_deref(),
+            UserError::WrongPassword.into(),
+        )
+        .await),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/me",
+    responses(
+        (status = 200, description = "Delete the authenticated user's account, clearing session cookies"),
+        (status = 401, description = "Missing or invalid credentials"),
+        (status = 403, description = "Not available to API keys")
+    )
+)]
+pub async fn delete_me(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> Result<(CookieJar, ApiResponse<()>), AppError> {
+    if user.is_api_key() {
+        return Err(AppError::Forbidden);
+    }
+
+    match usecases::delete_user(&state.db, user.id, None).await {
+        DomainResult::Ok(_) if user.session_id.is_some() => Ok((
+            cookies::cleared_cookies(&state.config.session),
+            ApiResponse::ok_empty(),
+        )),
+        DomainResult::Ok(_) => Ok((CookieJar::new(), ApiResponse::ok_empty())),
+        DomainResult::NotFound => Err(AppError::Unauthorized),
+        DomainResult::Err(e) => Err(e.into()),
+    }
+}
+
+/// Soft-deleted accounts are visible only to those who could delete them
+async fn require_deleted_access(state: &AppState, user: &AuthUser) -> Result<(), AppError> {
+    require_permission(state, user, Permission::UsersDelete).await
+}
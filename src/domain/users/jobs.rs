@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{error, info};
+
+use super::usecases;
+use crate::app::config::config::UserRetention;
+use crate::shared::types::result::DomainResult;
+
+/// Periodically hard-deletes users whose soft delete is older than the
+/// retention window. Does nothing when `purge_interval_secs` is `0`.
+pub fn spawn_purge_deleted_users(pool: PgPool, retention: UserRetention) {
+    if retention.purge_interval_secs == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(retention.purge_interval_secs));
+
+        loop {
+            ticker.tick().await;
+
+            match usecases::purge_deleted_users(&pool, retention.purge_after_secs).await {
+                DomainResult::Ok(0) => {}
+                DomainResult::Ok(purged) => info!(purged, "purged soft-deleted users"),
+                DomainResult::Err(e) => error!(error = %e, "purging soft-deleted users failed"),
+                _ => {}
+            }
+        }
+    });
+}
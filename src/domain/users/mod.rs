@@ -1,6 +1,9 @@
 pub mod api_doc;
 pub mod dtos;
 pub mod entities;
+pub mod error;
 pub mod handlers;
+pub mod jobs;
+pub mod query;
 pub mod routes;
 pub mod usecases;
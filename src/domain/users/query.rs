@@ -0,0 +1,88 @@
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::shared::pagination::{Cursor, SortOrder};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Email,
+}
+
+impl UserSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt => "created_at",
+            UserSortField::UpdatedAt => "updated_at",
+            UserSortField::Email => "email",
+        }
+    }
+
+    /// SQL type the cursor value is cast to
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            UserSortField::CreatedAt | UserSortField::UpdatedAt => "TIMESTAMP",
+            UserSortField::Email => "VARCHAR",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQuery {
+    /// 1-based page number, ignored when `cursor` is set
+    pub page: Option<u32>,
+    /// Items per page (1-100, default 20)
+    pub per_page: Option<u32>,
+    /// Keyset cursor returned as `meta.next_cursor`
+    pub cursor: Option<String>,
+    /// Case-insensitive substring match on email
+    pub email: Option<String>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: UserSortField,
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrder,
+    /// Also list soft-deleted users
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+impl ListUsersQuery {
+    /// Decode the cursor, rejecting ones issued for a different sort field
+    pub fn decode_cursor(&self) -> Result<Option<Cursor>, String> {
+        let Some(raw) = &self.cursor else {
+            return Ok(None);
+        };
+
+        let cursor = Cursor::decode(raw)?;
+        if cursor.sort != self.sort.column() {
+            return Err("cursor does not match sort field".to_string());
+        }
+
+        Ok(Some(cursor))
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FindUserQuery {
+    /// Also find a soft-deleted user
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteUserQuery {
+    /// Delete immediately, with names and memberships, instead of soft-deleting
+    #[serde(default)]
+    pub permanent: bool,
+}
@@ -1,19 +1,47 @@
 use axum::Router;
-use axum::routing::get;
+use axum::routing::{delete, get, patch, post, put};
 
 use super::handlers;
 use crate::app::state::AppState;
+use crate::domain::roles::entities::Permission;
+use crate::domain::roles::guard::RequirePermission;
 
-pub fn router() -> Router<AppState> {
+pub fn router(state: &AppState) -> Router<AppState> {
     Router::new()
+        .route("/", post(handlers::create_user))
+        // the caller's own account, no permission needed
+        .route("/me", get(handlers::get_me))
+        .route("/me", patch(handlers::update_me))
+        .route("/me", delete(handlers::delete_me))
+        .route("/me/password", post(handlers::change_my_password))
         .route(
             "/",
-            get(handlers::get_all_users).post(handlers::create_user),
+            get(handlers::get_all_users)
+                .route_layer(RequirePermission::new(state, Permission::UsersRead)),
         )
         .route(
             "/{id}",
             get(handlers::find_one_user)
-                .put(handlers::update_user)
-                .delete(handlers::delete_user),
+                .route_layer(RequirePermission::new(state, Permission::UsersRead)),
+        )
+        .route(
+            "/{id}",
+            put(handlers::update_user)
+                .route_layer(RequirePermission::new(state, Permission::UsersWrite)),
+        )
+        .route(
+            "/{id}",
+            patch(handlers::patch_user)
+                .route_layer(RequirePermission::new(state, Permission::UsersWrite)),
+        )
+        .route(
+            "/{id}",
+            delete(handlers::delete_user)
+                .route_layer(RequirePermission::new(state, Permission::UsersDelete)),
+        )
+        .route(
+            "/{id}/restore",
+            post(handlers::restore_user)
+                .route_layer(RequirePermission::new(state, Permission::UsersDelete)),
         )
 }
@@ -1,48 +1,127 @@
 use std::collections::HashMap;
 
-use super::dtos::create::CreateUserRequest;
+use super::dtos::create::{CreateUserRequest, MAX_NAME_LOCALE_LEN};
+use super::dtos::patch::PatchUserRequest;
 use super::dtos::update::UpdateUserRequest;
 use super::entities::User;
 use super::entities::name_entity::UserNameEntity;
 use super::entities::people_name::PersonName;
 use super::entities::user_entity::UserEntity;
-use crate::shared::security::password::hash_password;
+use super::error::UserError;
+use super::query::{ListUsersQuery, UserSortField};
+use crate::app::config::config::PasswordPolicy;
+use crate::shared::pagination::{Cursor, Page, clamp_per_page, escape_like};
+use crate::shared::security::password::{PasswordHasher, PasswordMatch};
+use crate::shared::security::password_policy::{self, PasswordContext};
+use crate::shared::types::etag::EntityTags;
 use crate::shared::types::hash::Hash;
+use crate::shared::types::patch::Patch;
 use crate::shared::types::result::DomainResult;
-use sqlx::PgPool;
+use crate::shared::validation::Validator;
+use sqlx::{PgConnection, PgExecutor, PgPool, Postgres, QueryBuilder};
 use tracing::error;
 use uuid::Uuid;
 
 /// =========================
 /// GET ALL USERS (BONUS: FIX N+1)
 /// =========================
-pub async fn get_all_users(pool: &PgPool) -> DomainResult<Vec<User>, String> {
+pub async fn get_all_users(
+    pool: &PgPool,
+    query: &ListUsersQuery,
+    cursor: Option<Cursor>,
+) -> DomainResult<Page<User>, UserError> {
+    let per_page = clamp_per_page(query.per_page);
+    let sort = query.sort;
+    let order = query.order;
+
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
-    // 1️⃣ fetch users
-    let users = match sqlx::query_as::<_, UserEntity>(
-        r#"
-        SELECT id, email, password, created_at, updated_at
-        FROM users
-        ORDER BY created_at DESC
-        "#,
-    )
-    .fetch_all(&mut *tx)
-    .await
+    // 1️⃣ count matching users
+    let mut count_query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM users");
+    push_user_filters(&mut count_query, query);
+
+    let total = match count_query
+        .build_query_scalar::<i64>()
+        .fetch_one(&mut *tx)
+        .await
+    {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 2️⃣ fetch one page (one extra row tells whether there is a next page)
+    let mut page_query = QueryBuilder::<Postgres>::new(
+        "SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version FROM users",
+    );
+    push_user_filters(&mut page_query, query);
+
+    if let Some(cursor) = &cursor {
+        page_query
+            .push(format!(
+                " AND ({}, id) {} (",
+                sort.column(),
+                order.cursor_op()
+            ))
+            .push_bind(cursor.value.clone())
+            .push(format!("::{}, ", sort.sql_type()))
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    page_query
+        .push(format!(
+            " ORDER BY {col} {dir}, id {dir} LIMIT ",
+            col = sort.column(),
+            dir = order.as_sql()
+        ))
+        .push_bind(per_page as i64 + 1);
+
+    if let (None, Some(page)) = (&cursor, query.page) {
+        page_query
+            .push(" OFFSET ")
+            .push_bind((page.max(1) as i64 - 1) * per_page as i64);
+    }
+
+    let mut users = match page_query
+        .build_query_as::<UserEntity>()
+        .fetch_all(&mut *tx)
+        .await
     {
         Ok(u) => u,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    let next_cursor = if users.len() > per_page as usize {
+        users.truncate(per_page as usize);
+        users.last().map(|last| {
+            Cursor {
+                sort: sort.column().to_string(),
+                value: match sort {
+                    UserSortField::CreatedAt => last.created_at.to_string(),
+                    UserSortField::UpdatedAt => last.updated_at.to_string(),
+                    UserSortField::Email => last.email.clone(),
+                },
+                id: last.id,
+            }
+            .encode()
+        })
+    } else {
+        None
     };
 
     if users.is_empty() {
         tx.commit().await.ok();
-        return DomainResult::Ok(vec![]);
+        return DomainResult::Ok(Page {
+            items: vec![],
+            total,
+            next_cursor,
+        });
     }
 
-    // 2️⃣ fetch ALL names in one query (NO N+1)
+    // 3️⃣ fetch ALL names in one query (NO N+1)
     let user_ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
 
     let names = match sqlx::query_as::<_, UserNameEntity>(
@@ -57,10 +136,10 @@ pub async fn get_all_users(pool: &PgPool) -> DomainResult<Vec<User>, String> {
     .await
     {
         Ok(u) => u,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
-    // 3️⃣ group names by user_id
+    // 4️⃣ group names by user_id
     let mut name_map: HashMap<Uuid, HashMap<String, PersonName>> = HashMap::new();
 
     for n in names {
@@ -74,79 +153,116 @@ pub async fn get_all_users(pool: &PgPool) -> DomainResult<Vec<User>, String> {
         );
     }
 
-    // 4️⃣ assemble domain users
-    let result = users
+    // 5️⃣ assemble domain users
+    let items = users
         .into_iter()
         .map(|u| User {
             id: u.id,
             name: Hash::new(name_map.remove(&u.id).unwrap_or_default()),
             email: u.email,
             password: u.password,
+            verified_at: u.verified_at,
             created_at: u.created_at,
             updated_at: u.updated_at,
+            deleted_at: u.deleted_at,
+            version: u.version,
         })
         .collect();
 
-    DomainResult::Ok(result)
+    DomainResult::Ok(Page {
+        items,
+        total,
+        next_cursor,
+    })
 }
 
 /// =========================
 /// FIND ONE USER
 /// =========================
-pub async fn find_one_user(pool: &PgPool, id: Uuid) -> DomainResult<User, String> {
+/// Soft-deleted users are `NotFound` unless `include_deleted` is set.
+pub async fn find_one_user(
+    pool: &PgPool,
+    id: Uuid,
+    include_deleted: bool,
+) -> DomainResult<User, UserError> {
     let user = match sqlx::query_as::<_, UserEntity>(
         r#"
-        SELECT id, email, password, created_at, updated_at
+        SELECT id, email, password, verified_at, created_at, updated_at, deleted_at, version
         FROM users
-        WHERE id = $1
+        WHERE id = $1 AND ($2 OR deleted_at IS NULL)
         "#,
     )
     .bind(id)
+    .bind(include_deleted)
     .fetch_optional(pool)
     .await
     {
         Ok(Some(u)) => u,
         Ok(None) => return DomainResult::NotFound,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
-    let names = match sqlx::query_as::<_, UserNameEntity>(
+    let names = match find_names(pool, id).await {
+        Ok(n) => n,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    DomainResult::Ok(map_to_domain(user, names))
+}
+
+/// =========================
+/// FIND NAMES
+/// =========================
+/// Every localized name of one user, on a pool or inside a transaction
+pub async fn find_names(
+    executor: impl PgExecutor<'_>,
+    user_id: Uuid,
+) -> Result<Vec<UserNameEntity>, sqlx::Error> {
+    sqlx::query_as::<_, UserNameEntity>(
         r#"
         SELECT user_id, lang, first_name, middle_name, last_name
         FROM user_names
         WHERE user_id = $1
         "#,
     )
-    .bind(id)
-    .fetch_all(pool)
+    .bind(user_id)
+    .fetch_all(executor)
     .await
-    {
-        Ok(n) => n,
-        Err(e) => return DomainResult::Err(e.to_string()),
-    };
-
-    DomainResult::Ok(map_to_domain(user, names))
 }
 
 /// =========================
 /// CREATE USER
 /// =========================
-pub async fn create_user(pool: &PgPool, req: CreateUserRequest) -> DomainResult<User, String> {
-    let hashed_password = match hash_password(&req.password) {
+pub async fn create_user(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    policy: &PasswordPolicy,
+    req: CreateUserRequest,
+) -> DomainResult<User, UserError> {
+    let context = PasswordContext {
+        email: Some(&req.email),
+        names: name_parts(&req.name),
+    };
+    let violations = password_policy::check(policy, "password", &req.password, &context);
+    if !violations.is_empty() {
+        return DomainResult::Err(UserError::WeakPassword(violations));
+    }
+
+    let hashed_password = match hasher.hash(&req.password) {
         Ok(h) => h,
-        Err(e) => return DomainResult::Err(e),
+        Err(e) => return DomainResult::Err(UserError::Hashing(e)),
     };
 
     let mut tx = match pool.begin().await {
         Ok(tx) => tx,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
     let user = match sqlx::query_as::<_, UserEntity>(
         r#"
         INSERT INTO users (email, password)
         VALUES ($1, $2)
-        RETURNING id, email, password, created_at, updated_at
+        RETURNING id, email, password, verified_at, created_at, updated_at, deleted_at, version
         "#,
     )
     .bind(&req.email)
@@ -155,7 +271,7 @@ pub async fn create_user(pool: &PgPool, req: CreateUserRequest) -> DomainResult<
     .await
     {
         Ok(u) => u,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
     // 🔥 insert ALL locales dynamically
@@ -175,12 +291,22 @@ pub async fn create_user(pool: &PgPool, req: CreateUserRequest) -> DomainResult<
         .await;
 
         if let Err(e) = res {
-            return DomainResult::Err(e.to_string());
+            return DomainResult::Err(e.into());
         }
     }
 
+    // every new account starts as a member
+    let role_res = sqlx::query("INSERT INTO user_roles (user_id, role) VALUES ($1, 'member')")
+        .bind(user.id)
+        .execute(&mut *tx)
+        .await;
+
+    if let Err(e) = role_res {
+        return DomainResult::Err(e.into());
+    }
+
     if let Err(e) = tx.commit().await {
-        return DomainResult::Err(e.to_string());
+        return DomainResult::Err(e.into());
     }
 
     DomainResult::Ok(User {
@@ -188,110 +314,572 @@ pub async fn create_user(pool: &PgPool, req: CreateUserRequest) -> DomainResult<
         name: req.name,
         email: user.email,
         password: user.password,
+        verified_at: user.verified_at,
         created_at: user.created_at,
         updated_at: user.updated_at,
+        deleted_at: user.deleted_at,
+        version: user.version,
     })
 }
 
 /// =========================
-/// UPDATE USER
+/// UPDATE USER (REPLACE)
 /// =========================
+/// `PUT` semantics: email and every name locale are replaced as given.
+/// Yields the user alongside whether the email changed.
 pub async fn update_user(
     pool: &PgPool,
+    hasher: &PasswordHasher,
+    policy: &PasswordPolicy,
     id: Uuid,
     req: UpdateUserRequest,
-) -> DomainResult<User, String> {
+    if_match: Option<&EntityTags>,
+) -> DomainResult<(User, bool), UserError> {
+    if let Some(password) = &req.password {
+        let context = PasswordContext {
+            email: Some(&req.email),
+            names: name_parts(&req.name),
+        };
+        let violations = password_policy::check(policy, "password", password, &context);
+        if !violations.is_empty() {
+            return DomainResult::Err(UserError::WeakPassword(violations));
+        }
+    }
+
+    let password = match &req.password {
+        Some(p) => match hasher.hash(p) {
+            Ok(h) => Some(h),
+            Err(e) => return DomainResult::Err(UserError::Hashing(e)),
+        },
+        None => None,
+    };
+
     let mut tx = match pool.begin().await {
         Ok(t) => t,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
-    let password = match req.password {
-        Some(p) => match hash_password(&p) {
-            Ok(h) => Some(h),
-            Err(e) => return DomainResult::Err(e),
-        },
+    // 1️⃣ replace the row
+    let email_changed =
+        match write_user(&mut tx, id, Some(&req.email), password.as_deref(), if_match).await {
+            DomainResult::Ok(changed) => changed,
+            DomainResult::NotFound => return DomainResult::NotFound,
+            DomainResult::Err(e) => return DomainResult::Err(e),
+        };
+
+    // 2️⃣ replace every locale
+    let del_res = sqlx::query("DELETE FROM user_names WHERE user_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await;
+
+    if let Err(e) = del_res {
+        return DomainResult::Err(e.into());
+    }
+
+    for (lang, name) in &req.name.values {
+        if let Err(e) = upsert_name(&mut tx, id, lang, name).await {
+            return DomainResult::Err(e.into());
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    match find_one_user(pool, id, false).await {
+        DomainResult::Ok(user) => DomainResult::Ok((user, email_changed)),
+        DomainResult::NotFound => DomainResult::NotFound,
+        DomainResult::Err(e) => DomainResult::Err(e),
+    }
+}
+
+/// =========================
+/// PATCH USER (MERGE)
+/// =========================
+/// JSON Merge Patch (RFC 7396): only the members present change, and
+/// name locales are added, merged or removed one by one. The merged names
+/// are validated as a whole against `required_locales`. Yields the user
+/// alongside whether the email changed.
+pub async fn patch_user(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    policy: &PasswordPolicy,
+    required_locales: &[String],
+    id: Uuid,
+    patch: PatchUserRequest,
+    if_match: Option<&EntityTags>,
+) -> DomainResult<(User, bool), UserError> {
+    // 1️⃣ current state
+    let current = match find_one_user(pool, id, false).await {
+        DomainResult::Ok(u) => u,
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    };
+
+    if if_match.is_some_and(|tags| !tags.matches_version(current.version)) {
+        return DomainResult::Err(UserError::PreconditionFailed);
+    }
+    let merged_from = EntityTags::Tags(vec![current.version.to_string()]);
+
+    // 2️⃣ merge the names, remembering which locales changed
+    let mut names = current.name.values;
+    let mut touched: Vec<(String, bool)> = Vec::new();
+
+    if let Patch::Value(name_patch) = patch.name {
+        for (lang, value) in name_patch.values {
+            match value {
+                Some(name_patch) => {
+                    let merged = name_patch.apply(names.remove(&lang));
+                    names.insert(lang.clone(), merged);
+                    touched.push((lang, true));
+                }
+                None => {
+                    // removing an absent locale is a no-op, as in RFC 7396
+                    if names.remove(&lang).is_some() {
+                        touched.push((lang, false));
+                    }
+                }
+            }
+        }
+
+        let mut v = Validator::new(required_locales.to_vec());
+        v.locales(
+            "name.values",
+            &names,
+            MAX_NAME_LOCALE_LEN,
+            |v, path, name| name.validate(v, path),
+        );
+        if let Err(errors) = v.finish() {
+            return DomainResult::Err(UserError::Invalid(errors));
+        }
+    }
+
+    let names = Hash::new(names);
+    let email = patch.email.as_value().map(String::as_str);
+
+    // 3️⃣ check a new password against the details after this patch
+    let password = match patch.password.as_value() {
+        Some(password) => {
+            let context = PasswordContext {
+                email: Some(email.unwrap_or(&current.email)),
+                names: name_parts(&names),
+            };
+            let violations = password_policy::check(policy, "password", password, &context);
+            if !violations.is_empty() {
+                return DomainResult::Err(UserError::WeakPassword(violations));
+            }
+
+            match hasher.hash(password) {
+                Ok(h) => Some(h),
+                Err(e) => return DomainResult::Err(UserError::Hashing(e)),
+            }
+        }
         None => None,
     };
 
-    let user_res = sqlx::query_as::<_, UserEntity>(
+    let mut tx = match pool.begin().await {
+        Ok(t) => t,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 4️⃣ write only what changed, provided nobody else did since 1️⃣
+    let email_changed =
+        match write_user(&mut tx, id, email, password.as_deref(), Some(&merged_from)).await {
+            DomainResult::Ok(changed) => changed,
+            DomainResult::NotFound => return DomainResult::NotFound,
+            DomainResult::Err(e) => return DomainResult::Err(e),
+        };
+
+    for (lang, kept) in &touched {
+        let res = match names.get(lang) {
+            Some(name) if *kept => upsert_name(&mut tx, id, lang, name).await,
+            _ => sqlx::query("DELETE FROM user_names WHERE user_id = $1 AND lang = $2")
+                .bind(id)
+                .bind(lang)
+                .execute(&mut *tx)
+                .await
+                .map(|_| ()),
+        };
+
+        if let Err(e) = res {
+            return DomainResult::Err(e.into());
+        }
+    }
+
+    if let Err(e) = tx.commit().await {
+        return DomainResult::Err(e.into());
+    }
+
+    match find_one_user(pool, id, false).await {
+        DomainResult::Ok(user) => DomainResult::Ok((user, email_changed)),
+        DomainResult::NotFound => DomainResult::NotFound,
+        DomainResult::Err(e) => DomainResult::Err(e),
+    }
+}
+
+/// Update the `users` row, keeping the email or password when `None`.
+/// Yields whether the email actually changed.
+async fn write_user(
+    tx: &mut PgConnection,
+    id: Uuid,
+    email: Option<&str>,
+    password: Option<&str>,
+    if_match: Option<&EntityTags>,
+) -> DomainResult<bool, UserError> {
+    let current_email = match lock_user(&mut *tx, id, false, if_match).await {
+        DomainResult::Ok(e) => e,
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    };
+
+    let res = sqlx::query(
         r#"
         UPDATE users
         SET
             email = COALESCE($1, email),
             password = COALESCE($2, password),
-            updated_at = CURRENT_TIMESTAMP
+            -- a new address has to be verified again
+            verified_at = CASE WHEN $1 IS NULL OR $1 = email THEN verified_at END,
+            updated_at = CURRENT_TIMESTAMP,
+            version = version + 1
         WHERE id = $3
-        RETURNING id, email, password, created_at, updated_at
         "#,
     )
-    .bind(req.email)
+    .bind(email)
     .bind(password)
     .bind(id)
-    .fetch_optional(&mut *tx)
+    .execute(tx)
     .await;
 
-    let _user = match user_res {
-        Ok(Some(u)) => u,
+    match res {
+        Ok(_) => DomainResult::Ok(email.is_some_and(|e| e != current_email)),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// Lock the row and check `If-Match` against its version, yielding the
+/// current email. Soft-deleted users only count when `include_deleted` is set.
+async fn lock_user(
+    tx: &mut PgConnection,
+    id: Uuid,
+    include_deleted: bool,
+    if_match: Option<&EntityTags>,
+) -> DomainResult<String, UserError> {
+    let (version, email) = match sqlx::query_as::<_, (i64, String)>(
+        "SELECT version, email FROM users WHERE id = $1 AND ($2 OR deleted_at IS NULL) FOR UPDATE",
+    )
+    .bind(id)
+    .bind(include_deleted)
+    .fetch_optional(tx)
+    .await
+    {
+        Ok(Some(v)) => v,
         Ok(None) => return DomainResult::NotFound,
-        Err(e) => return DomainResult::Err(e.to_string()),
+        Err(e) => return DomainResult::Err(e.into()),
     };
 
-    if let Some(names) = req.name {
-        let del_res = sqlx::query("DELETE FROM user_names WHERE user_id = $1")
-            .bind(id)
-            .execute(&mut *tx)
-            .await;
+    match if_match {
+        Some(tags) if !tags.matches_version(version) => {
+            DomainResult::Err(UserError::PreconditionFailed)
+        }
+        _ => DomainResult::Ok(email),
+    }
+}
 
-        if let Err(e) = del_res {
-            return DomainResult::Err(e.to_string());
+async fn upsert_name(
+    tx: &mut PgConnection,
+    user_id: Uuid,
+    lang: &str,
+    name: &PersonName,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO user_names (user_id, lang, first_name, middle_name, last_name)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id, lang) DO UPDATE
+        SET first_name = EXCLUDED.first_name,
+            middle_name = EXCLUDED.middle_name,
+            last_name = EXCLUDED.last_name
+        "#,
+    )
+    .bind(user_id)
+    .bind(lang)
+    .bind(&name.first)
+    .bind(&name.middle)
+    .bind(&name.last)
+    .execute(tx)
+    .await?;
+
+    Ok(())
+}
+
+/// =========================
+/// CHANGE PASSWORD
+/// =========================
+/// Self-service change: `current_password` must match before the policy
+/// applies. In the same transaction every refresh token and every session
+/// but `keep_session` is revoked, so the old password's logins end
+/// everywhere else.
+pub async fn change_password(
+    pool: &PgPool,
+    hasher: &PasswordHasher,
+    policy: &PasswordPolicy,
+    id: Uuid,
+    current_password: &str,
+    new_password: &str,
+    keep_session: Option<Uuid>,
+) -> DomainResult<(), UserError> {
+    // 1️⃣ check the current password
+    let user = match find_one_user(pool, id, false).await {
+        DomainResult::Ok(u) => u,
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    };
+
+    match hasher.verify(current_password, &user.password) {
+        Ok(PasswordMatch::Yes | PasswordMatch::NeedsRehash) => {}
+        Ok(PasswordMatch::No) => return DomainResult::Err(UserError::WrongPassword),
+        Err(e) => return DomainResult::Err(UserError::Hashing(e)),
+    }
+
+    // 2️⃣ check the new one against the policy
+    let context = PasswordContext {
+        email: Some(&user.email),
+        names: name_parts(&user.name),
+    };
+    let violations = password_policy::check(policy, "new_password", new_password, &context);
+    if !violations.is_empty() {
+        return DomainResult::Err(UserError::WeakPassword(violations));
+    }
+
+    let hash = match hasher.hash(new_password) {
+        Ok(h) => h,
+        Err(e) => return DomainResult::Err(UserError::Hashing(e)),
+    };
+
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    // 3️⃣ store it, unless the user changed since 1️⃣
+    let read_version = EntityTags::Tags(vec![user.version.to_string()]);
+    match write_user(&mut tx, id, None, Some(&hash), Some(&read_version)).await {
+        DomainResult::Ok(_) => {}
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    }
+
+    // 4️⃣ sign out everywhere else
+    let res = sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    let res = sqlx::query(
+        r#"
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND revoked_at IS NULL AND id IS DISTINCT FROM $2
+        "#,
+    )
+    .bind(id)
+    .bind(keep_session)
+    .execute(&mut *tx)
+    .await;
+
+    if let Err(e) = res {
+        return DomainResult::Err(e.into());
+    }
+
+    match tx.commit().await {
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// DELETE USER (SOFT)
+/// =========================
+/// Hides the user and ends every way of signing in as them, keeping
+/// names, memberships and the rest of their history until the purge.
+pub async fn delete_user(
+    pool: &PgPool,
+    id: Uuid,
+    if_match: Option<&EntityTags>,
+) -> DomainResult<(), UserError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match lock_user(&mut tx, id, false, if_match).await {
+        DomainResult::Ok(_) => {}
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    }
+
+    // 1️⃣ mark as deleted
+    let result = sqlx::query(
+        r#"
+        UPDATE users
+        SET deleted_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP, version = version + 1
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await;
+
+    match result {
+        Ok(_) => {}
+        Err(e) => {
+            error!("Error deleting user: {:?}", e);
+            return DomainResult::Err(e.into());
         }
+    }
 
-        for (lang, name) in names.values {
-            let res = sqlx::query(
-                r#"
-                INSERT INTO user_names (user_id, lang, first_name, middle_name, last_name)
-                VALUES ($1, $2, $3, $4, $5)
-                "#,
-            )
-            .bind(id)
-            .bind(lang)
-            .bind(name.first)
-            .bind(name.middle)
-            .bind(name.last)
-            .execute(&mut *tx)
-            .await;
-
-            if let Err(e) = res {
-                return DomainResult::Err(e.to_string());
-            }
+    // 2️⃣ revoke credentials; a restore requires signing in again
+    for revoke in [
+        "UPDATE refresh_tokens SET revoked_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND revoked_at IS NULL",
+        "UPDATE sessions SET revoked_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND revoked_at IS NULL",
+        "UPDATE api_keys SET revoked_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND revoked_at IS NULL",
+        "UPDATE password_reset_tokens SET used_at = CURRENT_TIMESTAMP WHERE user_id = $1 AND used_at IS NULL",
+    ] {
+        if let Err(e) = sqlx::query(revoke).bind(id).execute(&mut *tx).await {
+            return DomainResult::Err(e.into());
         }
     }
 
-    if let Err(e) = tx.commit().await {
-        return DomainResult::Err(e.to_string());
+    match tx.commit().await {
+        Ok(_) => DomainResult::Ok(()),
+        Err(e) => DomainResult::Err(e.into()),
     }
+}
+
+/// =========================
+/// RESTORE USER
+/// =========================
+pub async fn restore_user(pool: &PgPool, id: Uuid) -> DomainResult<User, UserError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE users
+        SET deleted_at = NULL, updated_at = CURRENT_TIMESTAMP, version = version + 1
+        WHERE id = $1 AND deleted_at IS NOT NULL
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await;
 
-    find_one_user(pool, id).await
+    match result {
+        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+        Ok(_) => find_one_user(pool, id, false).await,
+        Err(e) => DomainResult::Err(e.into()),
+    }
 }
 
 /// =========================
-/// DELETE USER
+/// PURGE USER (HARD DELETE)
 /// =========================
-pub async fn delete_user(pool: &PgPool, id: Uuid) -> DomainResult<(), String> {
+/// Deletes the row, cascading through names, memberships and credentials.
+pub async fn purge_user(
+    pool: &PgPool,
+    id: Uuid,
+    if_match: Option<&EntityTags>,
+) -> DomainResult<(), UserError> {
+    let mut tx = match pool.begin().await {
+        Ok(tx) => tx,
+        Err(e) => return DomainResult::Err(e.into()),
+    };
+
+    match lock_user(&mut tx, id, true, if_match).await {
+        DomainResult::Ok(_) => {}
+        DomainResult::NotFound => return DomainResult::NotFound,
+        DomainResult::Err(e) => return DomainResult::Err(e),
+    }
+
     let result = sqlx::query("DELETE FROM users WHERE id = $1")
         .bind(id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
 
-    match result {
-        Ok(r) if r.rows_affected() == 0 => DomainResult::NotFound,
+    if let Err(e) = result {
+        error!("Error purging user: {:?}", e);
+        return DomainResult::Err(e.into());
+    }
+
+    match tx.commit().await {
         Ok(_) => DomainResult::Ok(()),
-        Err(e) => {
-            error!("Error deleting user: {:?}", e);
-            DomainResult::Err(e.to_string())
-        }
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// PURGE DELETED USERS
+/// =========================
+/// Hard-deletes users soft-deleted more than `retention_secs` ago,
+/// returning how many were removed.
+pub async fn purge_deleted_users(
+    pool: &PgPool,
+    retention_secs: i64,
+) -> DomainResult<u64, UserError> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM users
+        WHERE deleted_at IS NOT NULL
+          AND deleted_at < CURRENT_TIMESTAMP - make_interval(secs => $1)
+        "#,
+    )
+    .bind(retention_secs as f64)
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(r) => DomainResult::Ok(r.rows_affected()),
+        Err(e) => DomainResult::Err(e.into()),
+    }
+}
+
+/// =========================
+/// LIST FILTERS
+/// =========================
+fn push_user_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ListUsersQuery) {
+    builder.push(" WHERE TRUE");
+
+    if !query.include_deleted {
+        builder.push(" AND deleted_at IS NULL");
+    }
+
+    if let Some(email) = &query.email {
+        builder
+            .push(" AND email ILIKE ")
+            .push_bind(format!("%{}%", escape_like(email)));
+    }
+    if let Some(from) = query.created_from {
+        builder.push(" AND created_at >= ").push_bind(from);
     }
+    if let Some(to) = query.created_to {
+        builder.push(" AND created_at <= ").push_bind(to);
+    }
+}
+
+/// =========================
+/// PASSWORD CONTEXT
+/// =========================
+fn name_parts(names: &Hash<String, PersonName>) -> Vec<&str> {
+    names.values.values().flat_map(PersonName::parts).collect()
 }
 
 /// =========================
@@ -301,14 +889,7 @@ fn map_to_domain(entity: UserEntity, names: Vec<UserNameEntity>) -> User {
     let mut map = HashMap::new();
 
     for n in names {
-        map.insert(
-            n.lang,
-            PersonName {
-                first: n.first_name,
-                middle: n.middle_name,
-                last: n.last_name,
-            },
-        );
+        map.insert(n.lang.clone(), PersonName::from(n));
     }
 
     User {
@@ -316,7 +897,10 @@ fn map_to_domain(entity: UserEntity, names: Vec<UserNameEntity>) -> User {
         name: Hash::new(map),
         email: entity.email,
         password: entity.password,
+        verified_at: entity.verified_at,
         created_at: entity.created_at,
         updated_at: entity.updated_at,
+        deleted_at: entity.deleted_at,
+        version: entity.version,
     }
 }
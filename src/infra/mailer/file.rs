@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{Email, Mailer, SendFuture};
+
+/// Writes each message as an `.eml` file, for development and tests
+pub struct FileMailer {
+    from: String,
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    pub fn new(from: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            from: from.into(),
+            dir: dir.into(),
+        }
+    }
+}
+
+impl Mailer for FileMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a> {
+        Box::pin(async move {
+            let now = Utc::now();
+            let id = Uuid::new_v4();
+
+            let message = format!(
+                "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@localhost>\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+                self.from,
+                email.to,
+                email.subject,
+                now.to_rfc2822(),
+                id,
+                email.body,
+            );
+
+            tokio::fs::create_dir_all(&self.dir).await?;
+            let path = self
+                .dir
+                .join(format!("{}-{}.eml", now.format("%Y%m%dT%H%M%S"), id));
+            tokio::fs::write(path, message).await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use thiserror::Error;
+
+pub mod file;
+
+/// A plain-text message to a single recipient
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("delivery failed: {0}")]
+    Delivery(String),
+}
+
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), MailError>> + Send + 'a>>;
+
+/// Outgoing mail transport, shared through `AppState`.
+///
+/// `file::FileMailer` is the default; SMTP or API-based providers plug in
+/// by implementing this trait.
+pub trait Mailer: Send + Sync {
+    fn send<'a>(&'a self, email: &'a Email) -> SendFuture<'a>;
+}
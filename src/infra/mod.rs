@@ -1 +1,3 @@
 pub mod database;
+pub mod mailer;
+pub mod oidc;
@@ -0,0 +1,196 @@
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use super::{ExchangeFuture, IdentityClaims, IdentityProvider, OidcError, with_query};
+use crate::app::config::config::OidcProvider;
+
+/// Unknown `kid`s trigger at most one JWKS fetch per this interval
+const JWKS_MIN_REFETCH: Duration = Duration::from_secs(60);
+
+/// A real provider, configured through `OIDC_<NAME>_*`
+pub struct HttpProvider {
+    config: OidcProvider,
+    client: reqwest::Client,
+    /// Signing keys, refetched when a token names an unknown `kid`
+    jwks: RwLock<Option<CachedJwks>>,
+}
+
+struct CachedJwks {
+    keys: JwkSet,
+    fetched_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenEndpointResponse {
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    nonce: Option<String>,
+}
+
+impl HttpProvider {
+    pub fn new(config: OidcProvider, client: reqwest::Client) -> Self {
+        Self {
+            config,
+            client,
+            jwks: RwLock::new(None),
+        }
+    }
+
+    async fn decoding_key(
+        &self,
+        kid: Option<&str>,
+        alg: Algorithm,
+    ) -> Result<DecodingKey, OidcError> {
+        if let Some(cached) = self.jwks.read().await.as_ref()
+            && let Some(key) = find_key(&cached.keys, kid, alg)
+        {
+            return key;
+        }
+
+        // unknown key: the provider may have rotated them, but made-up
+        // `kid`s mustn't turn every request into a fetch
+        let mut cache = self.jwks.write().await;
+        if let Some(cached) = cache.as_ref() {
+            if let Some(key) = find_key(&cached.keys, kid, alg) {
+                return key;
+            }
+            if cached.fetched_at.elapsed() < JWKS_MIN_REFETCH {
+                return Err(OidcError::InvalidToken("unknown signing key".to_string()));
+            }
+        }
+
+        let keys: JwkSet = self
+            .client
+            .get(&self.config.jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let key = find_key(&keys, kid, alg)
+            .unwrap_or_else(|| Err(OidcError::InvalidToken("unknown signing key".to_string())));
+        *cache = Some(CachedJwks {
+            keys,
+            fetched_at: Instant::now(),
+        });
+        key
+    }
+
+    async fn verify(&self, id_token: &str) -> Result<IdentityClaims, OidcError> {
+        let header = decode_header(id_token).map_err(|e| OidcError::InvalidToken(e.to_string()))?;
+
+        // the token doesn't get to pick how it is checked
+        if !self.config.algorithms.contains(&header.alg) {
+            return Err(OidcError::InvalidToken(format!(
+                "algorithm {:?} not allowed",
+                header.alg
+            )));
+        }
+
+        let key = self.decoding_key(header.kid.as_deref(), header.alg).await?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| OidcError::InvalidToken(e.to_string()))?
+            .claims;
+
+        Ok(IdentityClaims {
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified,
+            nonce: claims.nonce,
+        })
+    }
+}
+
+/// The key named by `kid`, refused if the JWK pins a different `alg`
+fn find_key(
+    jwks: &JwkSet,
+    kid: Option<&str>,
+    alg: Algorithm,
+) -> Option<Result<DecodingKey, OidcError>> {
+    let jwk = match kid {
+        Some(kid) => jwks.find(kid),
+        None => jwks.keys.first(),
+    }?;
+
+    // `KeyAlgorithm` and `Algorithm` share variant names for signing algorithms
+    if let Some(key_alg) = jwk.common.key_algorithm
+        && format!("{:?}", key_alg) != format!("{:?}", alg)
+    {
+        return Some(Err(OidcError::InvalidToken(format!(
+            "key is for {:?}, token uses {:?}",
+            key_alg, alg
+        ))));
+    }
+
+    Some(DecodingKey::from_jwk(jwk).map_err(|e| OidcError::InvalidToken(e.to_string())))
+}
+
+impl IdentityProvider for HttpProvider {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> String {
+        with_query(
+            &self.config.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", &self.config.client_id),
+                ("redirect_uri", &self.config.redirect_uri),
+                ("scope", &self.config.scopes),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+    }
+
+    fn exchange_code<'a>(&'a self, code: &'a str, code_verifier: &'a str) -> ExchangeFuture<'a> {
+        Box::pin(async move {
+            let mut form = vec![
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &self.config.redirect_uri),
+                ("client_id", &self.config.client_id),
+                ("code_verifier", code_verifier),
+            ];
+            if let Some(secret) = &self.config.client_secret {
+                form.push(("client_secret", secret));
+            }
+
+            let response = self
+                .client
+                .post(&self.config.token_endpoint)
+                .form(&form)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(OidcError::Rejected(format!("{}: {}", status, body)));
+            }
+
+            let tokens: TokenEndpointResponse = response.json().await?;
+            self.verify(&tokens.id_token).await
+        })
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{ExchangeFuture, IdentityClaims, IdentityProvider, OidcError, with_query};
+use crate::shared::security::token::{constant_time_eq, generate_token, pkce_challenge};
+
+pub const MOCK_PROVIDER: &str = "mock";
+
+/// Path of the mock authorization endpoint, served by this application
+pub const MOCK_AUTHORIZE_PATH: &str = "/mock-oidc/authorize";
+
+const CODE_TTL: Duration = Duration::from_secs(60);
+
+/// In-process provider for development and integration tests.
+///
+/// Its authorization endpoint signs in whichever email is passed as
+/// `login_hint`, with a verified email and `mock|<email>` as subject.
+/// Codes live in memory and are redeemed without network access.
+pub struct MockProvider {
+    redirect_uri: String,
+    codes: Mutex<HashMap<String, PendingCode>>,
+}
+
+struct PendingCode {
+    email: String,
+    nonce: String,
+    code_challenge: String,
+    issued_at: Instant,
+}
+
+impl MockProvider {
+    pub fn new(redirect_uri: impl Into<String>) -> Self {
+        Self {
+            redirect_uri: redirect_uri.into(),
+            codes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// What the authorization endpoint does once `email` has "signed in"
+    pub fn issue_code(&self, email: &str, nonce: &str, code_challenge: &str) -> String {
+        let code = generate_token();
+        let mut codes = self.codes.lock().unwrap_or_else(|e| e.into_inner());
+
+        codes.retain(|_, pending| pending.issued_at.elapsed() < CODE_TTL);
+        codes.insert(
+            code.clone(),
+            PendingCode {
+                email: email.to_string(),
+                nonce: nonce.to_string(),
+                code_challenge: code_challenge.to_string(),
+                issued_at: Instant::now(),
+            },
+        );
+
+        code
+    }
+}
+
+impl IdentityProvider for MockProvider {
+    fn name(&self) -> &str {
+        MOCK_PROVIDER
+    }
+
+    fn authorization_url(&self, state: &str, nonce: &str, code_challenge: &str) -> String {
+        with_query(
+            MOCK_AUTHORIZE_PATH,
+            &[
+                ("response_type", "code"),
+                ("redirect_uri", &self.redirect_uri),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+    }
+
+    fn exchange_code<'a>(&'a self, code: &'a str, code_verifier: &'a str) -> ExchangeFuture<'a> {
+        Box::pin(async move {
+            let pending = self
+                .codes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(code)
+                .filter(|pending| pending.issued_at.elapsed() < CODE_TTL)
+                .ok_or_else(|| OidcError::Rejected("unknown or expired code".to_string()))?;
+
+            let challenge = pkce_challenge(code_verifier);
+            if !constant_time_eq(challenge.as_bytes(), pending.code_challenge.as_bytes()) {
+                return Err(OidcError::Rejected("PKCE verification failed".to_string()));
+            }
+
+            Ok(IdentityClaims {
+                subject: format!("mock|{}", pending.email),
+                email: Some(pending.email),
+                email_verified: true,
+                nonce: Some(pending.nonce),
+            })
+        })
+    }
+}
@@ -0,0 +1,5 @@
+pub mod app;
+pub mod domain;
+pub mod infra;
+pub mod server;
+pub mod shared;
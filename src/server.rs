@@ -1,11 +1,16 @@
 use std::net::SocketAddr;
-use tracing::info;
+use std::sync::Arc;
+use tracing::{info, warn};
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::{EnvFilter, fmt};
 
 use crate::app::config::config;
 use crate::app::middleware::trace;
 use crate::app::state::AppState;
+use crate::infra::mailer::file::FileMailer;
+use crate::infra::oidc::IdentityProviders;
+use crate::shared::security::jwt::JwtKeys;
+use crate::shared::security::password::PasswordHasher;
 
 #[tokio::main]
 pub async fn start() {
@@ -22,6 +27,12 @@ pub async fn start() {
         .with_span_events(FmtSpan::CLOSE)
         .init();
 
+    if config.oidc.mock_enabled {
+        warn!(
+            "OIDC_MOCK_ENABLED is set: /mock-oidc signs anyone in as any email, never use it outside local development"
+        );
+    }
+
     let db = crate::infra::database::setup::init(
         &config.database.url,
         config.database.max_connections,
@@ -30,12 +41,36 @@ pub async fn start() {
     .await
     .expect("failed to setup database");
 
+    let jwt = JwtKeys::from_config(&config.auth).expect("failed to load jwt keys");
+    let passwords =
+        PasswordHasher::from_config(&config.password_hashing).expect("invalid argon2 parameters");
+
     let state = AppState {
         db,
         config: config.clone(),
+        jwt,
+        passwords,
+        mailer: Arc::new(FileMailer::new(&config.mail.from, &config.mail.dir)),
+        oidc: IdentityProviders::from_config(&config.oidc),
     };
 
-    let app = crate::app::routes::router()
+    if let Some(email) = &config.auth.bootstrap_admin_email {
+        crate::domain::roles::usecases::bootstrap_admin(&state.db, email)
+            .await
+            .expect("failed to bootstrap admin");
+    }
+
+    crate::domain::users::jobs::spawn_purge_deleted_users(
+        state.db.clone(),
+        config.user_retention.clone(),
+    );
+    crate::domain::lockout::jobs::spawn_purge_expired(
+        state.db.clone(),
+        config.login_throttle.clone(),
+    );
+    crate::domain::oidc::jobs::spawn_purge_states(state.db.clone(), config.oidc.clone());
+
+    let app = crate::app::routes::router(&state)
         .layer(trace::global_trace_layer())
         .with_state(state);
 
@@ -44,5 +79,10 @@ pub async fn start() {
     info!("server is running on {}", addr);
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use super::error::AppError;
+
+/// `sqlx::Error` classified by Postgres SQLSTATE.
+///
+/// Deliberately not `std::error::Error`: the blanket `From` impl on
+/// `AppError` would otherwise swallow it as a 500. Domain error enums
+/// follow the same rule so each can map onto the right `AppError`.
+#[derive(Debug)]
+pub enum DbError {
+    /// 23505, carries the constraint name
+    UniqueViolation(String),
+    /// 23503, carries the constraint name
+    ForeignKeyViolation(String),
+    /// 23514, carries the constraint name
+    CheckViolation(String),
+    /// 23502, carries the column name
+    NotNullViolation(String),
+    /// 22001, e.g. a `VARCHAR(5)` receiving six characters
+    ValueTooLong,
+    /// 22003, e.g. a price outside `DECIMAL(10, 2)`
+    NumericOutOfRange,
+    /// Anything else: connection, pool, decoding, ...
+    Other(sqlx::Error),
+}
+
+impl DbError {
+    pub fn is_unique_violation(&self, constraint: &str) -> bool {
+        matches!(self, DbError::UniqueViolation(c) if c == constraint)
+    }
+
+    pub fn is_check_violation(&self, constraint: &str) -> bool {
+        matches!(self, DbError::CheckViolation(c) if c == constraint)
+    }
+}
+
+impl From<sqlx::Error> for DbError {
+    fn from(err: sqlx::Error) -> Self {
+        let (code, constraint, column) = match err.as_database_error() {
+            Some(db) => (
+                db.code().map(|c| c.into_owned()),
+                db.constraint().unwrap_or_default().to_string(),
+                db.try_downcast_ref::<sqlx::postgres::PgDatabaseError>()
+                    .and_then(|pg| pg.column())
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            None => return DbError::Other(err),
+        };
+
+        match code.as_deref() {
+            Some("23505") => DbError::UniqueViolation(constraint),
+            Some("23503") => DbError::ForeignKeyViolation(constraint),
+            Some("23514") => DbError::CheckViolation(constraint),
+            Some("23502") => DbError::NotNullViolation(column),
+            Some("22001") => DbError::ValueTooLong,
+            Some("22003") => DbError::NumericOutOfRange,
+            _ => DbError::Other(err),
+        }
+    }
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::UniqueViolation(c) => write!(f, "unique constraint `{}` violated", c),
+            DbError::ForeignKeyViolation(c) => write!(f, "foreign key `{}` violated", c),
+            DbError::CheckViolation(c) => write!(f, "check constraint `{}` violated", c),
+            DbError::NotNullViolation(c) => write!(f, "column `{}` must not be null", c),
+            DbError::ValueTooLong => write!(f, "value too long"),
+            DbError::NumericOutOfRange => write!(f, "numeric value out of range"),
+            DbError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Generic mapping, used when a domain has nothing more specific to say
+impl From<DbError> for AppError {
+    fn from(err: DbError) -> Self {
+        match err {
+            DbError::UniqueViolation(_) => AppError::conflict("Resource already exists"),
+            DbError::ForeignKeyViolation(_) => {
+                AppError::bad_request("Referenced resource does not exist")
+            }
+            DbError::CheckViolation(_) | DbError::NotNullViolation(_) => {
+                AppError::bad_request(err.to_string())
+            }
+            DbError::ValueTooLong => AppError::bad_request("Value too long"),
+            DbError::NumericOutOfRange => AppError::bad_request("Numeric value out of range"),
+            DbError::Other(e) => AppError::Internal(e.into()),
+        }
+    }
+}
@@ -1,65 +1,216 @@
 use axum::{
     Json,
-    http::StatusCode,
+    http::{
+        HeaderValue, StatusCode,
+        header::{CONTENT_TYPE, RETRY_AFTER},
+    },
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
 use tracing::error;
+use utoipa::ToSchema;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
 
 /// Application-wide error type
 #[derive(Debug)]
 pub enum AppError {
     BadRequest(String),
+    /// 400 pointing at the offending path segment, query key or JSON position
+    InvalidInput(String, Vec<FieldError>),
+    /// 422 listing every offending field
+    Validation(Vec<FieldError>),
     NotFound(String),
+    MethodNotAllowed,
     Unauthorized,
     Forbidden,
     Conflict(String),
+    PayloadTooLarge(String),
+    UnsupportedMediaType(String),
+    /// 429 with a `Retry-After` header, in seconds
+    TooManyRequests(i64),
+    /// Any other status with its own stable `code`
+    Problem(Box<ProblemDetails>),
     Internal(anyhow::Error),
 }
 
-/// JSON error response body
-#[derive(Serialize)]
-struct ErrorResponse {
-    status_code: u16,
-    message: String,
+/// A single field-level issue reported in `ProblemDetails.errors`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    /// JSON pointer-ish path of the field, e.g. `name.th.first`
+    pub field: String,
+    /// Stable machine-readable reason, e.g. `too_long`
+    pub code: String,
+    pub message: String,
+    /// Part of the request the field came from: `body`, `query` or `path`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// 1-based line in the JSON body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column in the JSON body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl FieldError {
+    pub fn new<F: Into<String>, M: Into<String>>(field: F, code: &str, message: M) -> Self {
+        Self {
+            field: field.into(),
+            code: code.to_string(),
+            message: message.into(),
+            location: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn located(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+}
+
+/// RFC 7807 `application/problem+json` error body
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    /// URI identifying the problem type, stable per `code`
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Short summary of the problem type (the HTTP reason phrase)
+    pub title: String,
+    pub status: u16,
+    /// Human-readable explanation of this occurrence
+    pub detail: String,
+    /// Request path that produced the problem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Stable machine-readable error code
+    pub code: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl ProblemDetails {
+    pub fn new<T: Into<String>>(status: StatusCode, code: &str, detail: T) -> Self {
+        Self {
+            problem_type: format!("urn:problem-type:{}", code),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail: detail.into(),
+            instance: None,
+            code: code.to_string(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn with_errors(mut self, errors: Vec<FieldError>) -> Self {
+        self.errors = errors;
+        self
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+        // kept in extensions so middleware can fill in `instance`
+        let mut response = (status, Json(self.clone())).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response.extensions_mut().insert(self);
+        response
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        match self {
-            AppError::BadRequest(msg) => json_error(StatusCode::BAD_REQUEST, msg),
+        let retry_after = match &self {
+            AppError::TooManyRequests(secs) => Some((*secs).max(1)),
+            _ => None,
+        };
+
+        let mut response = match self {
+            AppError::BadRequest(msg) => {
+                ProblemDetails::new(StatusCode::BAD_REQUEST, "bad_request", msg)
+            }
 
-            AppError::NotFound(msg) => json_error(StatusCode::NOT_FOUND, msg),
+            AppError::InvalidInput(msg, errors) => {
+                ProblemDetails::new(StatusCode::BAD_REQUEST, "invalid_input", msg)
+                    .with_errors(errors)
+            }
+
+            AppError::Validation(errors) => ProblemDetails::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation_failed",
+                "request validation failed",
+            )
+            .with_errors(errors),
 
-            AppError::Unauthorized => json_error(StatusCode::UNAUTHORIZED, "unauthorized".into()),
+            AppError::NotFound(msg) => ProblemDetails::new(StatusCode::NOT_FOUND, "not_found", msg),
 
-            AppError::Forbidden => json_error(StatusCode::FORBIDDEN, "forbidden".into()),
+            AppError::MethodNotAllowed => ProblemDetails::new(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "method_not_allowed",
+                "method not allowed",
+            ),
 
-            AppError::Conflict(msg) => json_error(StatusCode::CONFLICT, msg),
+            AppError::Unauthorized => {
+                ProblemDetails::new(StatusCode::UNAUTHORIZED, "unauthorized", "unauthorized")
+            }
+
+            AppError::Forbidden => {
+                ProblemDetails::new(StatusCode::FORBIDDEN, "forbidden", "forbidden")
+            }
+
+            AppError::Conflict(msg) => ProblemDetails::new(StatusCode::CONFLICT, "conflict", msg),
+
+            AppError::PayloadTooLarge(msg) => {
+                ProblemDetails::new(StatusCode::PAYLOAD_TOO_LARGE, "payload_too_large", msg)
+            }
+
+            AppError::UnsupportedMediaType(msg) => ProblemDetails::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_media_type",
+                msg,
+            ),
+
+            AppError::TooManyRequests(secs) => ProblemDetails::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "too_many_requests",
+                format!("too many attempts, retry in {} seconds", secs.max(1)),
+            ),
+
+            AppError::Problem(problem) => *problem,
 
             AppError::Internal(err) => {
                 // Log once, centrally
                 error!(error = ?err, "internal server error");
 
-                json_error(
+                ProblemDetails::new(
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    "internal server error".into(),
+                    "internal_error",
+                    "internal server error",
                 )
             }
         }
-    }
-}
+        .into_response();
 
-/// Helper to build JSON error responses
-fn json_error(status_code: StatusCode, message: String) -> Response {
-    (
-        status_code,
-        Json(ErrorResponse {
-            status_code: status_code.as_u16(),
-            message,
-        }),
-    )
-        .into_response()
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(secs));
+        }
+
+        response
+    }
 }
 
 /// Enable `?` operator everywhere
@@ -86,6 +237,10 @@ impl AppError {
         AppError::Conflict(msg.into())
     }
 
+    pub fn problem<T: Into<String>>(status: StatusCode, code: &str, msg: T) -> Self {
+        AppError::Problem(Box::new(ProblemDetails::new(status, code, msg)))
+    }
+
     pub fn internal_server_error<T: std::fmt::Display>(msg: T) -> Self {
         AppError::Internal(anyhow::anyhow!("{}", msg))
     }
@@ -1,10 +1,28 @@
-use super::error::AppError;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use super::error::{AppError, FieldError};
+use super::types::etag::EntityTags;
+use super::types::locale::Locale;
+use super::validation::{Validate, Validator};
+use crate::app::state::AppState;
 use axum::{
-    extract::{FromRequestParts, Path},
-    http::request::Parts,
+    body::Bytes,
+    extract::{
+        ConnectInfo, FromRef, FromRequest, FromRequestParts, Path, Query, RawPathParams, Request,
+        path::ErrorKind,
+        rejection::{BytesRejection, PathRejection},
+    },
+    http::{
+        HeaderMap, HeaderName, StatusCode,
+        header::{ACCEPT_LANGUAGE, CONTENT_TYPE, IF_MATCH, IF_NONE_MATCH},
+        request::Parts,
+    },
 };
 use serde::de::DeserializeOwned;
+use serde_json::error::Category;
 
+/// Path parameters; parse failures name the offending parameter.
 pub struct ValidatedPath<T>(pub T);
 
 impl<S, T> FromRequestParts<S> for ValidatedPath<T>
@@ -17,10 +35,379 @@ where
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         match Path::<T>::from_request_parts(parts, state).await {
             Ok(value) => Ok(ValidatedPath(value.0)),
-            Err(rejection) => {
-                let err_msg = rejection.body_text();
-                Err(AppError::bad_request(format!("{}", err_msg)))
+            Err(PathRejection::FailedToDeserializePathParams(err)) => {
+                // tuple extractors don't always report a key, map it back to the route's names
+                let params = match RawPathParams::from_request_parts(parts, state).await {
+                    Ok(params) => params
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                    Err(_) => Vec::new(),
+                };
+                Err(path_error(err.kind(), &params, err.body_text()))
             }
+            Err(rejection) => Err(AppError::internal_server_error(rejection.body_text())),
+        }
+    }
+}
+
+/// Query string; parse failures name the offending key.
+pub struct ValidatedQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+        match serde_path_to_error::deserialize(deserializer) {
+            Ok(value) => Ok(ValidatedQuery(value)),
+            Err(err) => {
+                let path = err.path().to_string();
+                let message = err.into_inner().to_string();
+                Err(AppError::InvalidInput(
+                    "invalid query string".to_string(),
+                    vec![field_error(&path, message, "query")],
+                ))
+            }
+        }
+    }
+}
+
+/// JSON body checked against its `Validate` rules.
+///
+/// Malformed bodies are a 400 with the line and column of the problem;
+/// type mismatches, missing fields and rule failures are a 422 listing
+/// every offending field.
+pub struct ValidatedJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let required_locales = AppState::from_ref(state).config.app.required_locales;
+
+        if !has_json_content_type(req.headers()) {
+            return Err(AppError::UnsupportedMediaType(
+                "expected `Content-Type: application/json`".to_string(),
+            ));
+        }
+
+        let bytes = Bytes::from_request(req, state).await.map_err(bytes_error)?;
+        let value: T = parse_json(&bytes)?;
+
+        let mut validator = Validator::new(required_locales);
+        value.validate(&mut validator);
+        validator.finish().map_err(AppError::Validation)?;
+
+        Ok(ValidatedJson(value))
+    }
+}
+
+fn has_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    essence == "application/json"
+        || (essence.starts_with("application/") && essence.ends_with("+json"))
+}
+
+fn bytes_error(rejection: BytesRejection) -> AppError {
+    if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge(rejection.body_text())
+    } else {
+        AppError::bad_request(rejection.body_text())
+    }
+}
+
+fn parse_json<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, AppError> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        json_error(&path, err.into_inner())
+    })?;
+
+    // reject trailing characters after the document
+    deserializer.end().map_err(|err| json_error(".", err))?;
+
+    Ok(value)
+}
+
+fn json_error(path: &str, err: serde_json::Error) -> AppError {
+    let (line, column) = (err.line(), err.column());
+
+    // position is reported separately
+    let message = err.to_string();
+    let message = message
+        .strip_suffix(&format!(" at line {} column {}", line, column))
+        .unwrap_or(&message)
+        .to_string();
+
+    match err.classify() {
+        Category::Data => {
+            AppError::Validation(vec![field_error(path, message, "body").at(line, column)])
+        }
+        Category::Syntax | Category::Eof => AppError::InvalidInput(
+            "malformed JSON body".to_string(),
+            vec![
+                FieldError::new(field_path(path, "body"), "invalid_json", message)
+                    .located("body")
+                    .at(line, column),
+            ],
+        ),
+        Category::Io => AppError::internal_server_error(message),
+    }
+}
+
+fn path_error(kind: &ErrorKind, params: &[(String, String)], detail: String) -> AppError {
+    let short = |ty: &str| ty.rsplit("::").next().unwrap_or(ty).to_string();
+
+    let (field, message) = match kind {
+        ErrorKind::ParseErrorAtKey {
+            key,
+            value,
+            expected_type,
+        } => (
+            key.clone(),
+            format!("cannot parse `{}` as {}", value, short(expected_type)),
+        ),
+        ErrorKind::ParseErrorAtIndex {
+            index,
+            value,
+            expected_type,
+        } => (
+            params
+                .get(*index)
+                .map(|(key, _)| key.clone())
+                .unwrap_or_else(|| index.to_string()),
+            format!("cannot parse `{}` as {}", value, short(expected_type)),
+        ),
+        ErrorKind::ParseError {
+            value,
+            expected_type,
+        } => (
+            key_for_value(params, value),
+            format!("cannot parse `{}` as {}", value, short(expected_type)),
+        ),
+        ErrorKind::InvalidUtf8InPathParam { key } => {
+            (key.clone(), "must be valid UTF-8".to_string())
+        }
+        ErrorKind::DeserializeError {
+            key,
+            value,
+            message,
+        } => (
+            key.clone(),
+            format!("invalid value `{}`: {}", value, message),
+        ),
+        ErrorKind::Message(message) => {
+            let key = params
+                .iter()
+                .find(|(_, value)| message.contains(&format!("`{}`", value)))
+                .map(|(key, _)| key.clone())
+                .unwrap_or_else(|| "path".to_string());
+            (key, message.clone())
+        }
+        // wrong arity or unsupported types are route definition bugs
+        _ => return AppError::internal_server_error(detail),
+    };
+
+    AppError::InvalidInput(
+        "invalid path parameter".to_string(),
+        vec![FieldError::new(field, "invalid_value", message).located("path")],
+    )
+}
+
+fn key_for_value(params: &[(String, String)], value: &str) -> String {
+    params
+        .iter()
+        .find(|(_, v)| v == value)
+        .map(|(key, _)| key.clone())
+        .unwrap_or_else(|| "path".to_string())
+}
+
+/// serde reports a missing field against its parent, so point at the field itself
+fn field_error(path: &str, message: String, location: &str) -> FieldError {
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+
+    match missing {
+        Some(name) => {
+            let field = match path {
+                "." => name.to_string(),
+                parent => format!("{}.{}", parent, name),
+            };
+            FieldError::new(field, "required", "is required").located(location)
         }
+        None => {
+            FieldError::new(field_path(path, location), "invalid_value", message).located(location)
+        }
+    }
+}
+
+/// `serde_path_to_error` uses `.` for the document root and `?` when unknown
+fn field_path(path: &str, location: &str) -> String {
+    match path {
+        "." | "?" => location.to_string(),
+        path => path.to_string(),
+    }
+}
+
+/// Locale negotiated from `?lang=` (takes precedence) or `Accept-Language`,
+/// followed by the configured fallback chain.
+///
+/// `?lang=*` or a request without either asks for every translation.
+pub struct AcceptLocale(pub Locale);
+
+impl<S> FromRequestParts<S> for AcceptLocale
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let fallback = AppState::from_ref(state).config.app.locale_fallback;
+
+        let lang = Query::<HashMap<String, String>>::try_from_uri(&parts.uri)
+            .ok()
+            .and_then(|Query(mut params)| params.remove("lang"));
+
+        if let Some(lang) = lang {
+            if lang.trim() == "*" {
+                return Ok(AcceptLocale(Locale::All));
+            }
+            let tags = lang.split(',').map(str::to_string).collect();
+            return Ok(AcceptLocale(Locale::preferred(tags, &fallback)));
+        }
+
+        let locale = match parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(header) if header.trim() != "*" => Locale::from_accept_language(header, &fallback),
+            _ => Locale::All,
+        };
+
+        Ok(AcceptLocale(locale))
+    }
+}
+
+/// `If-Match` precondition for writes; `None` when the header is absent.
+/// Compared strongly, so weak tags never match.
+pub struct IfMatch(pub Option<EntityTags>);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(IfMatch(entity_tags(&parts.headers, IF_MATCH, false)))
+    }
+}
+
+/// `If-None-Match` for conditional reads; `None` when the header is absent
+pub struct IfNoneMatch(pub Option<EntityTags>);
+
+impl<S> FromRequestParts<S> for IfNoneMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(IfNoneMatch(entity_tags(
+            &parts.headers,
+            IF_NONE_MATCH,
+            true,
+        )))
+    }
+}
+
+/// Every occurrence of `name` joined, since tags may be split across headers
+fn entity_tags(headers: &HeaderMap, name: HeaderName, weak: bool) -> Option<EntityTags> {
+    let values: Vec<&str> = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(EntityTags::parse(&values.join(","), weak))
+}
+
+/// Client address: with `TRUST_PROXY_HEADERS` set, the `X-Forwarded-For`
+/// entry appended by the outermost trusted proxy, `TRUSTED_PROXY_HOPS`
+/// from the right; otherwise the peer address. Entries further left are
+/// sent by the client and never used.
+///
+/// `None` when neither is available (e.g. in-process requests).
+pub struct ClientIp(pub Option<String>);
+
+impl<S> FromRequestParts<S> for ClientIp
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app = &AppState::from_ref(state).config.app;
+        let trust_proxy = app.trust_proxy_headers;
+        let hops = app.trusted_proxy_hops.max(1);
+
+        // every proxy appends, so repeated headers count as one list
+        let entries: Vec<&str> = parts
+            .headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+
+        let forwarded = entries
+            .iter()
+            .rev()
+            .nth(hops - 1)
+            .filter(|ip| !ip.is_empty())
+            .map(|ip| ip.to_string());
+
+        let ip = match forwarded {
+            Some(ip) if trust_proxy => Some(ip),
+            _ => parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string()),
+        };
+
+        Ok(ClientIp(ip))
     }
 }
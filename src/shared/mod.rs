@@ -1,5 +1,8 @@
+pub mod db_error;
 pub mod error;
 pub mod extractors;
+pub mod pagination;
 pub mod response;
 pub mod security;
 pub mod types;
+pub mod validation;
@@ -1,10 +1,12 @@
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header::ETAG},
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
 
+use super::pagination::PaginationMeta;
+
 /// Standard API response wrapper
 #[derive(Serialize)]
 pub struct ApiResponse<T> {
@@ -12,6 +14,8 @@ pub struct ApiResponse<T> {
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<PaginationMeta>,
 }
 
 impl<T> IntoResponse for ApiResponse<T>
@@ -36,6 +40,7 @@ where
             status_code: status.as_u16(),
             message,
             data,
+            meta: None,
         }
     }
 
@@ -44,10 +49,36 @@ where
         Self::new(StatusCode::OK, "success".to_string(), Some(data))
     }
 
+    /// 200 OK list response with pagination metadata
+    pub fn paginated(data: T, meta: PaginationMeta) -> Self {
+        Self {
+            meta: Some(meta),
+            ..Self::ok(data)
+        }
+    }
+
     /// 201 Created response
     pub fn created(data: T) -> Self {
         Self::new(StatusCode::CREATED, "created".to_string(), Some(data))
     }
+
+    /// Same response carrying an `ETag` header
+    pub fn with_etag(self, etag: &str) -> Response {
+        let mut response = self.into_response();
+        if let Ok(value) = HeaderValue::from_str(etag) {
+            response.headers_mut().insert(ETAG, value);
+        }
+        response
+    }
+}
+
+/// 304 Not Modified, no body
+pub fn not_modified(etag: &str) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        response.headers_mut().insert(ETAG, value);
+    }
+    response
 }
 
 impl ApiResponse<()> {
@@ -55,4 +86,9 @@ impl ApiResponse<()> {
     pub fn ok_empty() -> Self {
         Self::new(StatusCode::OK, "success".to_string(), None)
     }
+
+    /// 202 Accepted, work continues in the background
+    pub fn accepted() -> Self {
+        Self::new(StatusCode::ACCEPTED, "accepted".to_string(), None)
+    }
 }
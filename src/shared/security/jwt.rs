@@ -0,0 +1,88 @@
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, errors::Error};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::app::config::config::{Auth, JwtAlgorithm};
+
+/// Access token claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub email: String,
+    pub iss: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Signing and verification keys built once from `AppConfig`
+#[derive(Clone)]
+pub struct JwtKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    algorithm: Algorithm,
+    issuer: String,
+    ttl_secs: i64,
+}
+
+impl JwtKeys {
+    pub fn from_config(auth: &Auth) -> Result<Self, Error> {
+        let (encoding, decoding, algorithm) = match auth.jwt_algorithm {
+            JwtAlgorithm::HS256 => {
+                let secret = auth.jwt_secret.as_deref().unwrap_or_default().as_bytes();
+                if secret.is_empty() {
+                    return Err(jsonwebtoken::errors::ErrorKind::InvalidKeyFormat.into());
+                }
+                (
+                    EncodingKey::from_secret(secret),
+                    DecodingKey::from_secret(secret),
+                    Algorithm::HS256,
+                )
+            }
+            JwtAlgorithm::RS256 => {
+                let private = auth.jwt_private_key.as_deref().unwrap_or_default();
+                let public = auth.jwt_public_key.as_deref().unwrap_or_default();
+                (
+                    EncodingKey::from_rsa_pem(private.as_bytes())?,
+                    DecodingKey::from_rsa_pem(public.as_bytes())?,
+                    Algorithm::RS256,
+                )
+            }
+        };
+
+        Ok(Self {
+            encoding,
+            decoding,
+            algorithm,
+            issuer: auth.jwt_issuer.clone(),
+            ttl_secs: auth.access_token_ttl_secs,
+        })
+    }
+
+    /// Lifetime of issued access tokens in seconds
+    pub fn ttl_secs(&self) -> i64 {
+        self.ttl_secs
+    }
+
+    /// Sign a new access token for the given user
+    pub fn issue(&self, user_id: Uuid, email: &str) -> Result<String, Error> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user_id,
+            email: email.to_string(),
+            iss: self.issuer.clone(),
+            iat: now,
+            exp: now + self.ttl_secs,
+        };
+
+        jsonwebtoken::encode(&Header::new(self.algorithm), &claims, &self.encoding)
+    }
+
+    /// Verify signature, issuer and expiry of an access token
+    pub fn verify(&self, token: &str) -> Result<Claims, Error> {
+        let mut validation = Validation::new(self.algorithm);
+        validation.set_issuer(&[&self.issuer]);
+
+        jsonwebtoken::decode::<Claims>(token, &self.decoding, &validation).map(|data| data.claims)
+    }
+}
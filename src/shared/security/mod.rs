@@ -1 +1,4 @@
+pub mod jwt;
 pub mod password;
+pub mod password_policy;
+pub mod token;
@@ -1,22 +1,124 @@
 use argon2::{
-    Argon2,
-    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Algorithm, Argon2, Params, Version,
+    password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString},
 };
 use rand_core::OsRng;
 
-pub fn hash_password(password: &str) -> Result<String, String> {
-    let salt = SaltString::generate(&mut OsRng);
+use crate::app::config::config::{Argon2Variant, PasswordHashing};
 
-    Argon2::default()
-        .hash_password(password.as_bytes(), &salt)
-        .map(|hash| hash.to_string())
-        .map_err(|e| e.to_string())
+/// Outcome of checking a password against a stored hash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordMatch {
+    No,
+    Yes,
+    /// Correct, but the hash uses outdated parameters or predates the pepper
+    NeedsRehash,
 }
 
-pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
-    let parsed_hash = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+/// Argon2 with the configured variant and cost, optionally keyed with a pepper
+#[derive(Clone)]
+pub struct PasswordHasher {
+    algorithm: Algorithm,
+    params: Params,
+    pepper: Option<Vec<u8>>,
+    /// Hash of no one's password, with the current parameters
+    dummy_hash: String,
+}
+
+impl PasswordHasher {
+    pub fn from_config(config: &PasswordHashing) -> Result<Self, String> {
+        let params = Params::new(
+            config.memory_kib,
+            config.iterations,
+            config.parallelism,
+            None,
+        )
+        .map_err(|e| e.to_string())?;
+
+        let algorithm = match config.variant {
+            Argon2Variant::Argon2d => Algorithm::Argon2d,
+            Argon2Variant::Argon2i => Algorithm::Argon2i,
+            Argon2Variant::Argon2id => Algorithm::Argon2id,
+        };
+
+        let mut hasher = Self {
+            algorithm,
+            params,
+            pepper: config.pepper.clone().map(String::into_bytes),
+            dummy_hash: String::new(),
+        };
+        hasher.dummy_hash = hasher.hash("dummy password")?;
+
+        Ok(hasher)
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String, String> {
+        let salt = SaltString::generate(&mut OsRng);
+
+        self.argon2(self.pepper.as_deref())?
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn verify(&self, password: &str, hash: &str) -> Result<PasswordMatch, String> {
+        let parsed_hash = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+
+        // variant and cost are read from the hash itself, only the pepper comes from us
+        if self
+            .argon2(self.pepper.as_deref())?
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok()
+        {
+            return Ok(if self.is_current(&parsed_hash) {
+                PasswordMatch::Yes
+            } else {
+                PasswordMatch::NeedsRehash
+            });
+        }
+
+        // hashes stored before a pepper was configured
+        if self.pepper.is_some()
+            && self
+                .argon2(None)?
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok()
+        {
+            return Ok(PasswordMatch::NeedsRehash);
+        }
+
+        Ok(PasswordMatch::No)
+    }
+
+    /// Spend the time a failed [`verify`](Self::verify) would, for callers
+    /// that have no stored hash to check against
+    pub fn verify_dummy(&self, password: &str) {
+        let _ = self.verify(password, &self.dummy_hash);
+    }
+
+    fn argon2<'a>(&self, pepper: Option<&'a [u8]>) -> Result<Argon2<'a>, String> {
+        match pepper {
+            Some(secret) => {
+                Argon2::new_with_secret(secret, self.algorithm, Version::V0x13, self.params.clone())
+                    .map_err(|e| e.to_string())
+            }
+            None => Ok(Argon2::new(
+                self.algorithm,
+                Version::V0x13,
+                self.params.clone(),
+            )),
+        }
+    }
+
+    fn is_current(&self, hash: &PasswordHash) -> bool {
+        let Ok(params) = Params::try_from(hash) else {
+            return false;
+        };
 
-    Ok(Argon2::default()
-        .verify_password(password.as_bytes(), &parsed_hash)
-        .is_ok())
+        hash.algorithm == self.algorithm.ident()
+            && hash.version == Some(Version::V0x13.into())
+            && params.m_cost() == self.params.m_cost()
+            && params.t_cost() == self.params.t_cost()
+            && params.p_cost() == self.params.p_cost()
+    }
 }
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use crate::app::config::config::PasswordPolicy;
+use crate::shared::error::FieldError;
+
+/// Lowercased, one per line
+static COMMON_PASSWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    include_str!("common_passwords.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+});
+
+/// Personal details a password must not contain
+#[derive(Debug, Default)]
+pub struct PasswordContext<'a> {
+    pub email: Option<&'a str>,
+    pub names: Vec<&'a str>,
+}
+
+impl<'a> PasswordContext<'a> {
+    /// Fragments worth checking: the email, its local part and every name part
+    fn fragments(&self) -> Vec<String> {
+        let mut fragments: Vec<&str> = self.names.clone();
+
+        if let Some(email) = self.email {
+            fragments.push(email);
+            if let Some((local, _)) = email.split_once('@') {
+                fragments.push(local);
+            }
+        }
+
+        fragments
+            .into_iter()
+            .map(|fragment| fragment.trim().to_lowercase())
+            // too short to be meaningful, e.g. `Li` or `Ng`
+            .filter(|fragment| fragment.chars().count() >= 3)
+            .collect()
+    }
+}
+
+/// Check `password` against `policy`, reporting every violated rule under `field`
+pub fn check(
+    policy: &PasswordPolicy,
+    field: &str,
+    password: &str,
+    context: &PasswordContext,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let len = password.chars().count();
+
+    if len < policy.min_length {
+        errors.push(FieldError::new(
+            field,
+            "too_short",
+            format!("must be at least {} characters", policy.min_length),
+        ));
+    }
+    if len > policy.max_length {
+        errors.push(FieldError::new(
+            field,
+            "too_long",
+            format!("must be at most {} characters", policy.max_length),
+        ));
+        // don't bother scanning oversized input any further
+        return errors;
+    }
+
+    let classes = [
+        (
+            policy.require_lowercase,
+            password.chars().any(char::is_lowercase),
+            "missing_lowercase",
+            "must contain a lowercase letter",
+        ),
+        (
+            policy.require_uppercase,
+            password.chars().any(char::is_uppercase),
+            "missing_uppercase",
+            "must contain an uppercase letter",
+        ),
+        (
+            policy.require_digit,
+            password.chars().any(|c| c.is_ascii_digit()),
+            "missing_digit",
+            "must contain a digit",
+        ),
+        (
+            policy.require_symbol,
+            password.chars().any(|c| !c.is_alphanumeric()),
+            "missing_symbol",
+            "must contain a symbol",
+        ),
+    ];
+
+    for (required, present, code, message) in classes {
+        if required && !present {
+            errors.push(FieldError::new(field, code, message));
+        }
+    }
+
+    let lowered = password.to_lowercase();
+
+    if policy.reject_common && COMMON_PASSWORDS.contains(lowered.as_str()) {
+        errors.push(FieldError::new(
+            field,
+            "common_password",
+            "is too common, choose a less predictable password",
+        ));
+    }
+
+    if context
+        .fragments()
+        .iter()
+        .any(|fragment| lowered.contains(fragment.as_str()))
+    {
+        errors.push(FieldError::new(
+            field,
+            "contains_personal_info",
+            "must not contain your email address or name",
+        ));
+    }
+
+    errors
+}
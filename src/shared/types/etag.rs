@@ -0,0 +1,125 @@
+/// Entity tags listed in `If-Match` or `If-None-Match`.
+///
+/// Tags are `"<version>"` or `"<version>-<variant>"`: the row `version`,
+/// plus whatever else shaped the representation (e.g. the locales of a
+/// localized body).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityTags {
+    /// `*`: any current representation
+    Any,
+    /// Opaque tags, without quotes
+    Tags(Vec<String>),
+}
+
+impl EntityTags {
+    /// Parse a header value. `weak` accepts `W/` tags, which only the
+    /// weak comparison of `If-None-Match` may use.
+    pub fn parse(header: &str, weak: bool) -> Self {
+        if header.trim() == "*" {
+            return EntityTags::Any;
+        }
+
+        let tags = header
+            .split(',')
+            .filter_map(|tag| {
+                let tag = tag.trim();
+                let tag = match tag.strip_prefix("W/") {
+                    Some(tag) if weak => tag,
+                    Some(_) => return None,
+                    None => tag,
+                };
+                Some(tag.strip_prefix('"')?.strip_suffix('"')?.to_string())
+            })
+            .collect();
+
+        EntityTags::Tags(tags)
+    }
+
+    /// Whether any tag names `version`, whatever its variant. Used for
+    /// `If-Match`, where every representation of a version is current.
+    pub fn matches_version(&self, version: i64) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::Tags(tags) => tags.iter().any(|tag| {
+                let prefix = tag.split_once('-').map_or(tag.as_str(), |(v, _)| v);
+                prefix.parse() == Ok(version)
+            }),
+        }
+    }
+
+    /// Whether any tag is exactly `etag`, as made by [`etag`]. Used for
+    /// `If-None-Match`, which must tell representations apart.
+    pub fn matches(&self, etag: &str) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::Tags(tags) => tags
+                .iter()
+                .any(|tag| etag.strip_prefix('"').and_then(|e| e.strip_suffix('"')) == Some(tag)),
+        }
+    }
+}
+
+/// Strong `ETag` for a row `version`, and `variant` when the body depends
+/// on more than the row
+pub fn etag(version: i64, variant: &str) -> String {
+    if variant.is_empty() {
+        format!("\"{}\"", version)
+    } else {
+        format!("\"{}-{}\"", version, variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> EntityTags {
+        EntityTags::Tags(tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    #[test]
+    fn parses_lists_and_wildcards() {
+        assert_eq!(EntityTags::parse("*", false), EntityTags::Any);
+        assert_eq!(EntityTags::parse(" * ", true), EntityTags::Any);
+        assert_eq!(
+            EntityTags::parse(r#""1", "2-en" ,"3""#, false),
+            tags(&["1", "2-en", "3"])
+        );
+    }
+
+    #[test]
+    fn drops_unquoted_tags() {
+        assert_eq!(EntityTags::parse(r#"1, "2", "3"#, false), tags(&["2"]));
+    }
+
+    #[test]
+    fn weak_tags_only_when_allowed() {
+        assert_eq!(EntityTags::parse(r#"W/"1", "2""#, true), tags(&["1", "2"]));
+        assert_eq!(EntityTags::parse(r#"W/"1", "2""#, false), tags(&["2"]));
+        assert!(!EntityTags::parse(r#"W/"1""#, false).matches_version(1));
+    }
+
+    #[test]
+    fn matches_version_ignores_the_variant() {
+        let parsed = EntityTags::parse(r#""4-en.th""#, false);
+        assert!(parsed.matches_version(4));
+        assert!(!parsed.matches_version(5));
+        assert!(EntityTags::Any.matches_version(5));
+        assert!(!tags(&["not-a-version"]).matches_version(0));
+    }
+
+    #[test]
+    fn matches_compares_the_whole_tag() {
+        let parsed = EntityTags::parse(r#""4-en""#, true);
+        assert!(parsed.matches(&etag(4, "en")));
+        assert!(!parsed.matches(&etag(4, "th")));
+        assert!(!parsed.matches(&etag(4, "")));
+        assert!(EntityTags::Any.matches(&etag(4, "th")));
+    }
+
+    #[test]
+    fn formats_tags() {
+        assert_eq!(etag(3, ""), r#""3""#);
+        assert_eq!(etag(3, "en.th"), r#""3-en.th""#);
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::locale::Locale;
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(bound(
     deserialize = "K: std::cmp::Eq + std::hash::Hash + Deserialize<'de>, V: Deserialize<'de>"
@@ -31,3 +33,13 @@ impl<K, V> Hash<K, V> {
         self.values.get(key).cloned().unwrap_or_default()
     }
 }
+
+impl<V> Hash<String, V> {
+    /// Keep only the best match for `locale`; `Locale::All` keeps everything
+    pub fn localize(mut self, locale: &Locale) -> Self {
+        if let Some(key) = locale.negotiate(&self.values).cloned() {
+            self.values.retain(|k, _| *k == key);
+        }
+        self
+    }
+}
@@ -6,3 +6,76 @@ use serde::{Deserialize, Serialize};
 pub struct LocalizedString {
     pub values: HashMap<String, String>,
 }
+
+/// Locale requested by the client
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Locale {
+    /// Every translation (`?lang=*` or no preference at all)
+    #[default]
+    All,
+    /// Language tags in priority order, fallback chain already appended
+    Preferred(Vec<String>),
+}
+
+impl Locale {
+    /// Build a preference list from the client's tags followed by the fallback chain
+    pub fn preferred(tags: Vec<String>, fallback: &[String]) -> Self {
+        let mut chain: Vec<String> = Vec::with_capacity(tags.len() + fallback.len());
+        for tag in tags.into_iter().chain(fallback.iter().cloned()) {
+            let tag = tag.trim().to_ascii_lowercase();
+            if !tag.is_empty() && tag != "*" && !chain.contains(&tag) {
+                chain.push(tag);
+            }
+        }
+        Locale::Preferred(chain)
+    }
+
+    /// Parse an `Accept-Language` header, ordering tags by quality
+    pub fn from_accept_language(header: &str, fallback: &[String]) -> Self {
+        let mut ranges: Vec<(String, f32)> = header
+            .split(',')
+            .filter_map(|part| {
+                let mut pieces = part.split(';');
+                let tag = pieces.next()?.trim().to_string();
+                let quality = pieces
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+
+        // stable sort keeps header order for equal weights
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Self::preferred(ranges.into_iter().map(|(tag, _)| tag).collect(), fallback)
+    }
+
+    /// Key of the best matching translation; any translation is the last resort
+    pub fn negotiate<'a, V>(&self, values: &'a HashMap<String, V>) -> Option<&'a String> {
+        let Locale::Preferred(chain) = self else {
+            return None;
+        };
+
+        for tag in chain {
+            // exact match, e.g. `th` → `th`
+            if let Some(key) = values.keys().find(|k| k.eq_ignore_ascii_case(tag)) {
+                return Some(key);
+            }
+            // same primary language, e.g. `en-US` → `en`
+            let primary = primary_subtag(tag);
+            if let Some(key) = values
+                .keys()
+                .find(|k| primary_subtag(k).eq_ignore_ascii_case(primary))
+            {
+                return Some(key);
+            }
+        }
+
+        values.keys().min()
+    }
+}
+
+fn primary_subtag(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or(tag)
+}
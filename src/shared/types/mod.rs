@@ -1,4 +1,6 @@
+pub mod etag;
 pub mod hash;
 pub mod locale;
+pub mod patch;
 pub mod people_name;
 pub mod result;
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+use super::error::FieldError;
+
+/// Request bodies checked by `ValidatedJson` before reaching handlers
+pub trait Validate {
+    fn validate(&self, v: &mut Validator);
+}
+
+/// Collects every failing rule instead of stopping at the first one
+#[derive(Debug, Default)]
+pub struct Validator {
+    required_locales: Vec<String>,
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new(required_locales: Vec<String>) -> Self {
+        Self {
+            required_locales,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, field: &str, code: &str, message: impl Into<String>) -> &mut Self {
+        self.errors.push(FieldError::new(field, code, message));
+        self
+    }
+
+    pub fn required(&mut self, field: &str, value: &str) -> &mut Self {
+        if value.trim().is_empty() {
+            self.add(field, "required", "must not be empty");
+        }
+        self
+    }
+
+    /// Length in characters, inclusive bounds
+    pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) -> &mut Self {
+        let len = value.chars().count();
+        if len < min {
+            self.add(
+                field,
+                "too_short",
+                format!("must be at least {} characters", min),
+            );
+        } else if len > max {
+            self.add(
+                field,
+                "too_long",
+                format!("must be at most {} characters", max),
+            );
+        }
+        self
+    }
+
+    pub fn email(&mut self, field: &str, value: &str) -> &mut Self {
+        if !is_email(value) {
+            self.add(field, "invalid_email", "must be a valid email address");
+        }
+        self.length(field, value, 0, 255)
+    }
+
+    /// Validate a locale-keyed map: BCP 47 keys no longer than `max_tag_len`,
+    /// configured required locales present, and each value via `each`.
+    pub fn locales<V>(
+        &mut self,
+        field: &str,
+        values: &HashMap<String, V>,
+        max_tag_len: usize,
+        mut each: impl FnMut(&mut Self, &str, &V),
+    ) -> &mut Self {
+        if values.is_empty() {
+            self.add(field, "required", "must contain at least one locale");
+        }
+
+        for required in self.required_locales.clone() {
+            if !values.keys().any(|k| k.eq_ignore_ascii_case(&required)) {
+                self.add(
+                    &format!("{}.{}", field, required),
+                    "missing_locale",
+                    format!("locale `{}` is required", required),
+                );
+            }
+        }
+
+        let mut keys: Vec<&String> = values.keys().collect();
+        keys.sort();
+        for key in keys {
+            let path = format!("{}.{}", field, key);
+            if !is_bcp47(key) {
+                self.add(&path, "invalid_locale", "must be a BCP 47 language tag");
+            } else if key.len() > max_tag_len {
+                self.add(
+                    &path,
+                    "invalid_locale",
+                    format!("locale tag must be at most {} characters", max_tag_len),
+                );
+            }
+            each(self, &path, &values[key]);
+        }
+        self
+    }
+
+    pub fn finish(self) -> Result<(), Vec<FieldError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Pragmatic address check: `local@domain.tld`, no whitespace
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Structural BCP 47 check: 2-3 or 5-8 letter language, then 1-8 alphanumeric subtags
+pub fn is_bcp47(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+
+    let Some(language) = subtags.next() else {
+        return false;
+    };
+    let language_ok = matches!(language.len(), 2..=3 | 5..=8)
+        && language.chars().all(|c| c.is_ascii_alphabetic());
+
+    language_ok
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
@@ -0,0 +1,174 @@
+// each test binary uses a different subset of these helpers
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, Request, StatusCode};
+use serde_json::Value;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+use crud_rust::app::config::config::AppConfig;
+use crud_rust::app::state::AppState;
+use crud_rust::infra::mailer::file::FileMailer;
+use crud_rust::infra::oidc::IdentityProviders;
+use crud_rust::shared::security::jwt::JwtKeys;
+use crud_rust::shared::security::password::PasswordHasher;
+
+/// Configuration from the environment, adjusted for tests
+pub fn config() -> AppConfig {
+    let mut config = AppConfig::load().expect("failed to load config");
+    config.app.env = "test".into();
+    config
+        .auth
+        .jwt_secret
+        .get_or_insert_with(|| "test-secret".into());
+    config.oidc.mock_enabled = true;
+    config
+}
+
+/// The application on top of a `#[sqlx::test]` database, with the mock
+/// identity provider enabled
+pub fn app(pool: PgPool) -> Router {
+    app_with(pool, config())
+}
+
+/// Same as [`app`], with a configuration the test has adjusted
+pub fn app_with(pool: PgPool, config: AppConfig) -> Router {
+    let state = AppState {
+        db: pool,
+        jwt: JwtKeys::from_config(&config.auth).expect("invalid jwt config"),
+        passwords: PasswordHasher::from_config(&config.password_hashing)
+            .expect("invalid argon2 parameters"),
+        mailer: Arc::new(FileMailer::new(
+            &config.mail.from,
+            std::env::temp_dir()
+                .join("crud-rust-test-mail")
+                .to_str()
+                .unwrap(),
+        )),
+        oidc: IdentityProviders::from_config(&config.oidc),
+        config,
+    };
+
+    crud_rust::app::routes::router(&state).with_state(state)
+}
+
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Value,
+}
+
+pub async fn send(app: &Router, request: Request<Body>) -> TestResponse {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+
+    TestResponse {
+        status,
+        headers,
+        body,
+    }
+}
+
+pub fn get(uri: &str) -> Request<Body> {
+    Request::get(uri).body(Body::empty()).unwrap()
+}
+
+pub fn post_json(uri: &str, body: Value) -> Request<Body> {
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// Insert a user directly, returning its ID
+pub async fn create_user(pool: &PgPool, email: &str) -> uuid::Uuid {
+    sqlx::query_scalar("INSERT INTO users (email, password) VALUES ($1, 'x') RETURNING id")
+        .bind(email)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Insert a verified user who can sign in with `password`, returning its ID
+pub async fn create_user_with_password(pool: &PgPool, email: &str, password: &str) -> uuid::Uuid {
+    let hash = PasswordHasher::from_config(&config().password_hashing)
+        .expect("invalid argon2 parameters")
+        .hash(password)
+        .unwrap();
+
+    sqlx::query_scalar(
+        "INSERT INTO users (email, password, verified_at) VALUES ($1, $2, CURRENT_TIMESTAMP) RETURNING id",
+    )
+    .bind(email)
+    .bind(hash)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// `POST /auth/login` with a JSON body
+pub async fn login(app: &Router, email: &str, password: &str) -> TestResponse {
+    send(
+        app,
+        post_json(
+            "/auth/login",
+            serde_json::json!({ "email": email, "password": password }),
+        ),
+    )
+    .await
+}
+
+/// Access token for a user, signed as the app would sign it
+pub fn access_token(user_id: uuid::Uuid, email: &str) -> String {
+    JwtKeys::from_config(&config().auth)
+        .expect("invalid jwt config")
+        .issue(user_id, email)
+        .unwrap()
+}
+
+/// `request` sent with a Bearer token
+pub fn bearer(mut request: Request<Body>, token: &str) -> Request<Body> {
+    request
+        .headers_mut()
+        .insert(AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+    request
+}
+
+pub async fn create_school(pool: &PgPool) -> uuid::Uuid {
+    sqlx::query_scalar(
+        r#"INSERT INTO schools (name, acronym) VALUES ('{"en": "Test School"}', '{"en": "TS"}') RETURNING id"#,
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+pub async fn grant_role(pool: &PgPool, user_id: uuid::Uuid, role: &str) {
+    sqlx::query("INSERT INTO user_roles (user_id, role) VALUES ($1, $2)")
+        .bind(user_id)
+        .bind(role)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Mark the user as having a confirmed authenticator
+pub async fn enable_two_factor(pool: &PgPool, user_id: uuid::Uuid) {
+    sqlx::query(
+        "INSERT INTO user_totp (user_id, secret, confirmed_at) VALUES ($1, 'JBSWY3DPEHPK3PXP', CURRENT_TIMESTAMP)",
+    )
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::header::{ACCEPT_LANGUAGE, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use common::{
+    TestResponse, access_token, app, bearer, create_user, enable_two_factor, grant_role, send,
+};
+
+/// An app, an admin's token and a user named in English and Thai
+async fn setup(pool: &PgPool) -> (axum::Router, String, Uuid) {
+    let app = app(pool.clone());
+
+    let admin = create_user(pool, "admin@example.com").await;
+    grant_role(pool, admin, "admin").await;
+    enable_two_factor(pool, admin).await;
+
+    let user = create_user(pool, "alice@example.com").await;
+    sqlx::query(
+        r#"
+        INSERT INTO user_names (user_id, lang, first_name, middle_name, last_name)
+        VALUES ($1, 'en', 'Alice', '', 'Smith'), ($1, 'th', 'อลิซ', '', 'สมิธ')
+        "#,
+    )
+    .bind(user)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    (app, access_token(admin, "admin@example.com"), user)
+}
+
+fn request(
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<(&str, Value)>,
+) -> Request<Body> {
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+
+    match body {
+        Some((content_type, body)) => builder
+            .header(CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    }
+}
+
+async fn read(app: &axum::Router, token: &str, id: Uuid, headers: &[(&str, &str)]) -> TestResponse {
+    let uri = format!("/users/{}", id);
+    send(app, bearer(request("GET", &uri, headers, None), token)).await
+}
+
+fn etag(res: &TestResponse) -> String {
+    res.headers[ETAG].to_str().unwrap().to_string()
+}
+
+fn replacement() -> Value {
+    json!({
+        "name": { "values": { "en": { "first": "Alicia", "middle": "", "last": "Smith" } } },
+        "email": "alice@example.com"
+    })
+}
+
+#[sqlx::test]
+async fn unchanged_representation_is_not_modified(pool: PgPool) {
+    let (app, token, user) = setup(&pool).await;
+
+    let res = read(&app, &token, user, &[(ACCEPT_LANGUAGE.as_str(), "en")]).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let tag = etag(&res);
+
+    let res = read(
+        &app,
+        &token,
+        user,
+        &[
+            (ACCEPT_LANGUAGE.as_str(), "en"),
+            (IF_NONE_MATCH.as_str(), &tag),
+        ],
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::NOT_MODIFIED);
+
+    // weak comparison applies to If-None-Match
+    let weak = format!("W/{}", tag);
+    let res = read(
+        &app,
+        &token,
+        user,
+        &[
+            (ACCEPT_LANGUAGE.as_str(), "en"),
+            (IF_NONE_MATCH.as_str(), &weak),
+        ],
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::NOT_MODIFIED);
+}
+
+#[sqlx::test]
+async fn other_locales_are_other_representations(pool: PgPool) {
+    let (app, token, user) = setup(&pool).await;
+
+    let english = read(&app, &token, user, &[(ACCEPT_LANGUAGE.as_str(), "en")]).await;
+    let tag = etag(&english);
+
+    let res = read(
+        &app,
+        &token,
+        user,
+        &[
+            (ACCEPT_LANGUAGE.as_str(), "th"),
+            (IF_NONE_MATCH.as_str(), &tag),
+        ],
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_ne!(etag(&res), tag);
+}
+
+#[sqlx::test]
+async fn changes_invalidate_the_tag(pool: PgPool) {
+    let (app, token, user) = setup(&pool).await;
+    let tag = etag(&read(&app, &token, user, &[]).await);
+
+    let uri = format!("/users/{}", user);
+    let res = send(
+        &app,
+        bearer(
+            request("PUT", &uri, &[], Some(("application/json", replacement()))),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let res = read(&app, &token, user, &[(IF_NONE_MATCH.as_str(), &tag)]).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn writes_require_a_current_if_match(pool: PgPool) {
+    let (app, token, user) = setup(&pool).await;
+    let current = etag(&read(&app, &token, user, &[]).await);
+    let uri = format!("/users/{}", user);
+    let stale = [(IF_MATCH.as_str(), "\"999\"")];
+    let patch = json!({ "email": "alicia@example.com" });
+
+    let res = send(
+        &app,
+        bearer(
+            request(
+                "PUT",
+                &uri,
+                &stale,
+                Some(("application/json", replacement())),
+            ),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::PRECONDITION_FAILED, "{}", res.body);
+
+    let res = send(
+        &app,
+        bearer(
+            request(
+                "PATCH",
+                &uri,
+                &stale,
+                Some(("application/merge-patch+json", patch.clone())),
+            ),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::PRECONDITION_FAILED, "{}", res.body);
+
+    let res = send(&app, bearer(request("DELETE", &uri, &stale, None), &token)).await;
+    assert_eq!(res.status, StatusCode::PRECONDITION_FAILED, "{}", res.body);
+
+    // the current tag, whatever locales it was read in, is accepted
+    let res = send(
+        &app,
+        bearer(
+            request(
+                "PATCH",
+                &uri,
+                &[(IF_MATCH.as_str(), &current)],
+                Some(("application/merge-patch+json", patch)),
+            ),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn weak_tags_are_refused_for_if_match(pool: PgPool) {
+    let (app, token, user) = setup(&pool).await;
+    let weak = format!("W/{}", etag(&read(&app, &token, user, &[]).await));
+    let uri = format!("/users/{}", user);
+
+    let res = send(
+        &app,
+        bearer(
+            request(
+                "PUT",
+                &uri,
+                &[(IF_MATCH.as_str(), &weak)],
+                Some(("application/json", replacement())),
+            ),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::PRECONDITION_FAILED, "{}", res.body);
+
+    let res = send(
+        &app,
+        bearer(
+            request("DELETE", &uri, &[(IF_MATCH.as_str(), &weak)], None),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::PRECONDITION_FAILED, "{}", res.body);
+
+    let strong = weak.trim_start_matches("W/");
+    let res = send(
+        &app,
+        bearer(
+            request("DELETE", &uri, &[(IF_MATCH.as_str(), strong)], None),
+            &token,
+        ),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body.get("data").is_none(), "{}", res.body);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use axum::http::StatusCode;
+use axum::http::header::RETRY_AFTER;
+use sqlx::PgPool;
+
+use common::{app_with, config, create_user_with_password, login};
+
+const EMAIL: &str = "alice@example.com";
+const PASSWORD: &str = "correct horse battery";
+
+/// The app with an account locked after three failures
+fn app(pool: PgPool) -> axum::Router {
+    let mut config = config();
+    config.login_throttle.max_failures_per_account = 3;
+    app_with(pool, config)
+}
+
+#[sqlx::test]
+async fn locks_the_account_after_repeated_failures(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user_with_password(&pool, EMAIL, PASSWORD).await;
+
+    for _ in 0..2 {
+        let res = login(&app, EMAIL, "wrong password").await;
+        assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+    }
+
+    // the failure reaching the limit is already answered with the lock
+    let res = login(&app, EMAIL, "wrong password").await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS, "{}", res.body);
+    assert!(res.headers.contains_key(RETRY_AFTER));
+
+    // and the right password doesn't get through it
+    let res = login(&app, EMAIL, PASSWORD).await;
+    assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn successful_login_clears_the_failures(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user_with_password(&pool, EMAIL, PASSWORD).await;
+
+    for _ in 0..2 {
+        login(&app, EMAIL, "wrong password").await;
+    }
+    let res = login(&app, EMAIL, PASSWORD).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let throttled: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM login_throttles WHERE scope = 'account'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(throttled, 0);
+
+    // counting starts over
+    for _ in 0..2 {
+        let res = login(&app, EMAIL, "wrong password").await;
+        assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+    }
+}
+
+#[sqlx::test]
+async fn unknown_email_looks_like_a_wrong_password(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user_with_password(&pool, EMAIL, PASSWORD).await;
+
+    for _ in 0..2 {
+        let wrong_password = login(&app, EMAIL, "wrong password").await;
+        let unknown_email = login(&app, "nobody@example.com", "wrong password").await;
+
+        assert_eq!(wrong_password.status, StatusCode::UNAUTHORIZED);
+        assert_eq!(unknown_email.status, wrong_password.status);
+        assert_eq!(unknown_email.body, wrong_password.body);
+    }
+
+    // unknown addresses are throttled all the same
+    let wrong_password = login(&app, EMAIL, "wrong password").await;
+    let unknown_email = login(&app, "nobody@example.com", "wrong password").await;
+
+    assert_eq!(wrong_password.status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(unknown_email.status, wrong_password.status);
+    assert_eq!(unknown_email.body, wrong_password.body);
+}
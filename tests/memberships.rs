@@ -0,0 +1,79 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{Request, StatusCode};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+use common::{
+    access_token, app, bearer, create_school, create_user, enable_two_factor, grant_role,
+    post_json, send,
+};
+
+fn put_json(uri: &str, body: Value) -> Request<Body> {
+    Request::put(uri)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn null_left_at_reactivates_a_member(pool: PgPool) {
+    let app = app(pool.clone());
+    let admin = create_user(&pool, "admin@example.com").await;
+    grant_role(&pool, admin, "admin").await;
+    enable_two_factor(&pool, admin).await;
+    let token = access_token(admin, "admin@example.com");
+
+    let member = create_user(&pool, "member@example.com").await;
+    let school = create_school(&pool).await;
+    let members = format!("/schools/{}/members", school);
+    let membership = format!("{}/{}", members, member);
+
+    let res = send(
+        &app,
+        bearer(
+            post_json(
+                &members,
+                json!({
+                    "user_id": member,
+                    "role": "student",
+                    "enrolled_at": "2024-01-01",
+                    "left_at": "2024-06-30"
+                }),
+            ),
+            &token,
+        ),
+    )
+    .await;
+    assert!(res.status.is_success(), "{} {}", res.status, res.body);
+
+    // left out: kept
+    let res = send(
+        &app,
+        bearer(put_json(&membership, json!({ "role": "teacher" })), &token),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["data"]["role"], "teacher");
+    assert_eq!(res.body["data"]["left_at"], "2024-06-30");
+
+    // null: cleared
+    let res = send(
+        &app,
+        bearer(put_json(&membership, json!({ "left_at": null })), &token),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert_eq!(res.body["data"]["left_at"], Value::Null);
+    assert_eq!(res.body["data"]["enrolled_at"], "2024-01-01");
+
+    // columns that can't be empty
+    let res = send(
+        &app,
+        bearer(put_json(&membership, json!({ "role": null })), &token),
+    )
+    .await;
+    assert_eq!(res.status, StatusCode::UNPROCESSABLE_ENTITY, "{}", res.body);
+}
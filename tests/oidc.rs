@@ -0,0 +1,209 @@
+mod common;
+
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use axum::http::header::LOCATION;
+use serde_json::json;
+use sqlx::PgPool;
+
+use common::{app, create_user, get, post_json, send};
+
+/// Query parameters of a path-and-query URL
+fn query(url: &str) -> HashMap<String, String> {
+    let (_, query) = url.split_once('?').unwrap_or((url, ""));
+    form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+/// Start a login and return the mock authorization URL
+async fn authorize(app: &axum::Router) -> String {
+    let res = send(app, post_json("/auth/oidc/mock/authorize", json!({}))).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    res.body["data"]["authorization_url"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// Sign in at the mock provider with the given authorization request,
+/// returning `code` and `state` from the redirect back
+async fn redirect_back(app: &axum::Router, params: &HashMap<String, String>) -> (String, String) {
+    let url = format!(
+        "/mock-oidc/authorize?{}",
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish()
+    );
+    let res = send(app, get(&url)).await;
+    assert_eq!(res.status, StatusCode::SEE_OTHER);
+
+    let location = query(res.headers[LOCATION].to_str().unwrap());
+    (location["code"].clone(), location["state"].clone())
+}
+
+/// Sign in at the mock provider as `email`, as a browser following
+/// `authorization_url` would
+async fn sign_in_at_provider(
+    app: &axum::Router,
+    authorization_url: &str,
+    email: &str,
+) -> (String, String) {
+    let mut params = query(authorization_url);
+    params.insert("login_hint".into(), email.into());
+    redirect_back(app, &params).await
+}
+
+async fn callback(app: &axum::Router, code: &str, state: &str) -> common::TestResponse {
+    send(
+        app,
+        post_json(
+            "/auth/oidc/mock/callback",
+            json!({ "code": code, "state": state }),
+        ),
+    )
+    .await
+}
+
+#[sqlx::test]
+async fn login_through_mock_provider(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "alice@example.com").await;
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body["data"]["access_token"].is_string());
+    assert!(res.body["data"]["refresh_token"].is_string());
+}
+
+#[sqlx::test]
+async fn rejects_pkce_verifier_mismatch(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "alice@example.com").await;
+
+    // the provider is handed a challenge that isn't derived from our verifier
+    let mut params = query(&authorize(&app).await);
+    params.insert("code_challenge".into(), "A".repeat(43));
+    params.insert("login_hint".into(), "alice@example.com".into());
+    let (code, state) = redirect_back(&app, &params).await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn rejects_nonce_mismatch(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "alice@example.com").await;
+
+    let mut params = query(&authorize(&app).await);
+    params.insert("nonce".into(), "someone-elses-nonce".into());
+    params.insert("login_hint".into(), "alice@example.com".into());
+    let (code, state) = redirect_back(&app, &params).await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn rejects_reused_state(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "alice@example.com").await;
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+    assert_eq!(callback(&app, &code, &state).await.status, StatusCode::OK);
+
+    // a fresh code doesn't make the used state redeemable again
+    let (code, _) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+    assert_eq!(res.body["code"], "invalid_sso_state");
+}
+
+#[sqlx::test]
+async fn rejects_expired_state(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "alice@example.com").await;
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+
+    sqlx::query(
+        "UPDATE oidc_login_states SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 second'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+    assert_eq!(res.body["code"], "invalid_sso_state");
+}
+
+#[sqlx::test]
+async fn links_existing_user_by_verified_email(pool: PgPool) {
+    let app = app(pool.clone());
+    let user_id = create_user(&pool, "Alice@Example.com").await;
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+    let res = callback(&app, &code, &state).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    let (linked_user, subject): (uuid::Uuid, String) =
+        sqlx::query_as("SELECT user_id, subject FROM user_identities WHERE provider = 'mock'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(linked_user, user_id);
+    assert_eq!(subject, "mock|alice@example.com");
+
+    // the provider vouched for the address
+    let verified: bool =
+        sqlx::query_scalar("SELECT verified_at IS NOT NULL FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(verified);
+}
+
+#[sqlx::test]
+async fn refuses_unknown_email(pool: PgPool) {
+    let app = app(pool);
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "nobody@example.com").await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    assert_eq!(res.body["code"], "no_matching_account");
+}
+
+#[sqlx::test]
+async fn refuses_to_guess_between_case_variants(pool: PgPool) {
+    let app = app(pool.clone());
+    create_user(&pool, "Alice@Example.com").await;
+    create_user(&pool, "alice@example.com").await;
+
+    let authorization_url = authorize(&app).await;
+    let (code, state) = sign_in_at_provider(&app, &authorization_url, "alice@example.com").await;
+    let res = callback(&app, &code, &state).await;
+
+    assert_eq!(res.status, StatusCode::CONFLICT, "{}", res.body);
+    assert_eq!(res.body["code"], "ambiguous_account");
+
+    let linked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_identities")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(linked, 0);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use common::{app, create_user, post_json, send};
+use crud_rust::shared::security::token::hash_token;
+
+async fn issue_reset_token(pool: &PgPool, user_id: Uuid, token: &str) {
+    sqlx::query(
+        "INSERT INTO password_reset_tokens (user_id, token_hash, expires_at) VALUES ($1, $2, CURRENT_TIMESTAMP + INTERVAL '1 hour')",
+    )
+    .bind(user_id)
+    .bind(hash_token(token))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn reset(token: &str) -> axum::http::Request<axum::body::Body> {
+    post_json(
+        "/auth/password/reset",
+        json!({ "token": token, "new_password": "a-brand-new-password-9" }),
+    )
+}
+
+#[sqlx::test]
+async fn resets_the_password(pool: PgPool) {
+    let app = app(pool.clone());
+    let user_id = create_user(&pool, "alice@example.com").await;
+    issue_reset_token(&pool, user_id, "reset-token").await;
+
+    let res = send(&app, reset("reset-token")).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    // single use
+    let res = send(&app, reset("reset-token")).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn refuses_tokens_of_deleted_users(pool: PgPool) {
+    let app = app(pool.clone());
+    let user_id = create_user(&pool, "alice@example.com").await;
+    issue_reset_token(&pool, user_id, "reset-token").await;
+    sqlx::query("UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let res = send(&app, reset("reset-token")).await;
+    assert_eq!(res.status, StatusCode::BAD_REQUEST, "{}", res.body);
+
+    let password: String = sqlx::query_scalar("SELECT password FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(password, "x");
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+
+use common::{TestResponse, app, create_user_with_password, login, post_json, send};
+
+async fn refresh(app: &axum::Router, refresh_token: &str) -> TestResponse {
+    send(
+        app,
+        post_json("/auth/refresh", json!({ "refresh_token": refresh_token })),
+    )
+    .await
+}
+
+/// Sign in and return the first refresh token of a new family
+async fn signed_in(app: &axum::Router, pool: &PgPool) -> String {
+    create_user_with_password(pool, "alice@example.com", "correct horse battery").await;
+    let res = login(app, "alice@example.com", "correct horse battery").await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+
+    res.body["data"]["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[sqlx::test]
+async fn rotates_refresh_tokens(pool: PgPool) {
+    let app = app(pool.clone());
+    let first = signed_in(&app, &pool).await;
+
+    let res = refresh(&app, &first).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    assert!(res.body["data"]["access_token"].is_string());
+    let second = res.body["data"]["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(first, second);
+
+    let res = refresh(&app, &second).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn reuse_revokes_the_family(pool: PgPool) {
+    let app = app(pool.clone());
+    let first = signed_in(&app, &pool).await;
+
+    let res = refresh(&app, &first).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let second = res.body["data"]["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // replaying the rotated token looks like theft
+    let res = refresh(&app, &first).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+
+    // which also burns the legitimate successor
+    let res = refresh(&app, &second).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn rejects_expired_refresh_tokens(pool: PgPool) {
+    let app = app(pool.clone());
+    let token = signed_in(&app, &pool).await;
+
+    sqlx::query("UPDATE refresh_tokens SET expires_at = CURRENT_TIMESTAMP - INTERVAL '1 second'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let res = refresh(&app, &token).await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn rejects_unknown_refresh_tokens(pool: PgPool) {
+    let app = app(pool);
+
+    let res = refresh(&app, "not-a-refresh-token").await;
+    assert_eq!(res.status, StatusCode::UNAUTHORIZED, "{}", res.body);
+}
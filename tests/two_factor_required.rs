@@ -0,0 +1,80 @@
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use common::{
+    access_token, app, bearer, create_school, create_user, enable_two_factor, get, grant_role,
+    post_json, send,
+};
+
+/// An admin (a role requiring two-factor by default) and their token
+async fn admin(pool: &PgPool) -> (Uuid, String) {
+    let id = create_user(pool, "admin@example.com").await;
+    grant_role(pool, id, "admin").await;
+    (id, access_token(id, "admin@example.com"))
+}
+
+#[sqlx::test]
+async fn school_members_require_two_factor_for_global_access(pool: PgPool) {
+    let app = app(pool.clone());
+    let (admin_id, token) = admin(&pool).await;
+    let member = create_user(&pool, "member@example.com").await;
+    let school = create_school(&pool).await;
+    let members = format!("/schools/{}/members", school);
+    let add = json!({ "user_id": member, "role": "student" });
+
+    let res = send(&app, bearer(get(&members), &token)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    let res = send(&app, bearer(post_json(&members, add.clone()), &token)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    enable_two_factor(&pool, admin_id).await;
+
+    let res = send(&app, bearer(get(&members), &token)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+    let res = send(&app, bearer(post_json(&members, add), &token)).await;
+    assert!(res.status.is_success(), "{} {}", res.status, res.body);
+}
+
+#[sqlx::test]
+async fn other_users_schools_require_two_factor(pool: PgPool) {
+    let app = app(pool.clone());
+    let (admin_id, token) = admin(&pool).await;
+    let other = create_user(&pool, "other@example.com").await;
+    let uri = format!("/users/{}/schools", other);
+
+    let res = send(&app, bearer(get(&uri), &token)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    assert_eq!(res.body["code"], "two_factor_required");
+
+    enable_two_factor(&pool, admin_id).await;
+
+    let res = send(&app, bearer(get(&uri), &token)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}
+
+#[sqlx::test]
+async fn deleted_users_require_two_factor(pool: PgPool) {
+    let app = app(pool.clone());
+    let (admin_id, token) = admin(&pool).await;
+    let other = create_user(&pool, "other@example.com").await;
+    sqlx::query("UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(other)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let uri = format!("/users/{}?include_deleted=true", other);
+
+    let res = send(&app, bearer(get(&uri), &token)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+    let res = send(&app, bearer(get("/users?include_deleted=true"), &token)).await;
+    assert_eq!(res.status, StatusCode::FORBIDDEN, "{}", res.body);
+
+    enable_two_factor(&pool, admin_id).await;
+
+    let res = send(&app, bearer(get(&uri), &token)).await;
+    assert_eq!(res.status, StatusCode::OK, "{}", res.body);
+}